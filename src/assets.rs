@@ -0,0 +1,209 @@
+use asefile::{AnimationDirection, AsepriteFile, LayerType};
+use bevy::{
+  asset::{AssetLoader, LoadContext, LoadedAsset},
+  prelude::*,
+  reflect::TypeUuid,
+  render::texture::{Extent3d, TextureDimension, TextureFormat},
+  utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+/// Sprite sheet described in a `.sheet` RON file, see `assets/sheets`
+#[derive(Debug, Deserialize, TypeUuid)]
+#[uuid = "6c3a1e0e-4f3b-4a8f-9a0d-2d1f3c5b7e21"]
+pub struct SpriteSheet {
+  /// Image path relative to the assets folder
+  pub texture: String,
+  /// Size of the whole image
+  pub size: (f32, f32),
+  /// Point of the frame the sprite is anchored at, from the top left corner
+  /// in fractions of the frame size, (0.5, 0.5) is the center
+  pub pivot: (f32, f32),
+  pub frames: Vec<SpriteFrame>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpriteFrame {
+  /// Position and size of the frame in the image: x, y, width, height
+  pub rect: (f32, f32, f32, f32),
+  /// Overrides the frame rate of the animation state for this frame
+  #[serde(default)]
+  pub duration: Option<f32>,
+  /// Overrides the pivot of the sheet for this frame
+  #[serde(default)]
+  pub pivot: Option<(f32, f32)>,
+}
+
+/// Background layers described in a RON file, see `assets/background/parallax.ron`
+#[derive(Debug, Deserialize)]
+pub struct Parallax {
+  pub layers: Vec<ParallaxLayer>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ParallaxLayer {
+  /// Image path relative to the assets folder
+  pub texture: String,
+  /// Width of the image, the layer is tiled horizontally
+  pub width: f32,
+  pub z: f32,
+  /// How much the layer follows the camera horizontally, 1 is infinitely far
+  /// away and 0 moves together with the world
+  pub factor: f32,
+  /// Same for vertical movement, by default the layer keeps the camera height
+  #[serde(default = "ParallaxLayer::vertical_factor")]
+  pub vertical_factor: f32,
+  /// Offset from the camera in pixels
+  #[serde(default)]
+  pub offset: (f32, f32),
+}
+
+impl ParallaxLayer {
+  fn vertical_factor() -> f32 {
+    1.0
+  }
+}
+
+impl Parallax {
+  pub fn load(path: &str) -> anyhow::Result<Parallax> {
+    let text = std::fs::read_to_string(format!("assets/{}", path))?;
+    Ok(ron::de::from_str(&text)?)
+  }
+}
+
+#[derive(Default)]
+pub struct SpriteSheetLoader;
+
+impl AssetLoader for SpriteSheetLoader {
+  fn load<'a>(
+    &'a self,
+    bytes: &'a [u8],
+    load_context: &'a mut LoadContext,
+  ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+    Box::pin(async move {
+      let sheet: SpriteSheet = ron::de::from_bytes(bytes)?;
+      load_context.set_default_asset(LoadedAsset::new(sheet));
+      Ok(())
+    })
+  }
+
+  fn extensions(&self) -> &[&str] {
+    &["sheet"]
+  }
+}
+
+/// Animation authored in Aseprite, every image layer becomes a texture with all the frames
+/// in a row and every tag a named range of those frames
+#[derive(Debug, TypeUuid)]
+#[uuid = "0f5e2b7c-9a43-4d6e-8b1a-7c2d4e6f8a90"]
+pub struct Aseprite {
+  pub frame_size: (f32, f32),
+  /// Duration of every frame in seconds
+  pub durations: Vec<f32>,
+  pub layers: Vec<AsepriteLayer>,
+  pub tags: Vec<AsepriteTag>,
+}
+
+#[derive(Debug)]
+pub struct AsepriteLayer {
+  pub name: String,
+  pub texture: Handle<Texture>,
+}
+
+#[derive(Debug)]
+pub struct AsepriteTag {
+  pub name: String,
+  /// Frames in the order they are played, directions of the tag already applied
+  pub frames: Vec<u32>,
+}
+
+#[derive(Default)]
+pub struct AsepriteLoader;
+
+impl AssetLoader for AsepriteLoader {
+  fn load<'a>(
+    &'a self,
+    bytes: &'a [u8],
+    load_context: &'a mut LoadContext,
+  ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+    Box::pin(async move {
+      let ase = AsepriteFile::read(bytes)?;
+      let (width, height) = (ase.width() as u32, ase.height() as u32);
+      let frame_count = ase.num_frames();
+
+      let mut layers = Vec::new();
+
+      for layer_id in 0..ase.num_layers() {
+        let layer = ase.layer(layer_id);
+
+        // groups only hold other layers and have no pixels of their own
+        if !layer.is_visible() || layer.layer_type() == LayerType::Group {
+          continue;
+        }
+
+        // frames of the layer side by side in one texture
+        let row = (width * frame_count * 4) as usize;
+        let mut data = vec![0; row * height as usize];
+
+        for frame in 0..frame_count {
+          let image = ase.frame(frame).layer(layer_id).image();
+
+          for (y, pixels) in image.into_raw().chunks((width * 4) as usize).enumerate() {
+            let start = y * row + (frame * width * 4) as usize;
+            data[start..start + pixels.len()].copy_from_slice(pixels);
+          }
+        }
+
+        let texture = Texture::new(
+          Extent3d::new(width * frame_count, height, 1),
+          TextureDimension::D2,
+          data,
+          TextureFormat::Rgba8UnormSrgb,
+        );
+
+        layers.push(AsepriteLayer {
+          name: layer.name().to_string(),
+          texture: load_context.set_labeled_asset(layer.name(), LoadedAsset::new(texture)),
+        });
+      }
+
+      let tags = (0..ase.num_tags())
+        .map(|tag_id| {
+          let tag = ase.tag(tag_id);
+          let forward: Vec<u32> = (tag.from_frame()..=tag.to_frame()).collect();
+
+          let frames = match tag.animation_direction() {
+            AnimationDirection::Forward => forward,
+            AnimationDirection::Reverse => forward.into_iter().rev().collect(),
+            AnimationDirection::PingPong => {
+              let back = forward.iter().rev().skip(1);
+              let back = back.take(forward.len().saturating_sub(2));
+              forward.iter().chain(back).copied().collect()
+            }
+          };
+
+          AsepriteTag {
+            name: tag.name().to_string(),
+            frames,
+          }
+        })
+        .collect();
+
+      let aseprite = Aseprite {
+        frame_size: (width as f32, height as f32),
+        durations: (0..frame_count)
+          .map(|frame| ase.frame(frame).duration() as f32 / 1000.0)
+          .collect(),
+        layers,
+        tags,
+      };
+
+      load_context.set_default_asset(LoadedAsset::new(aseprite));
+      Ok(())
+    })
+  }
+
+  fn extensions(&self) -> &[&str] {
+    &["aseprite", "ase"]
+  }
+}
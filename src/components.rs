@@ -1,35 +1,758 @@
 use bevy::prelude::*;
+use std::sync::Arc;
 
+use super::constants::{
+  BOSS_CHARGE_SPEED, BOSS_LEAP_HEIGHT, BOSS_LEAP_SPEED, CAMERA_DAMPING, CAMERA_DEADZONE_X,
+  CAMERA_DEADZONE_Y, CAMERA_LOOK_AHEAD, PLAYER_AIR_DASHES,
+};
+use super::events::{GameEvent, Signal};
+
+/// Plays an animation graph on the sprite sheet of an entity
+pub struct Animator {
+  pub graph: Arc<AnimationGraph>,
+  pub state: usize,
+  pub timer: Timer,
+  pub is_finished: bool,
+  /// Facts about the entity the transitions are checked against
+  pub params: AnimationParams,
+}
+
+impl Animator {
+  pub fn new(graph: Arc<AnimationGraph>) -> Animator {
+    let fps = graph.states.first().map_or(0.0, |state| state.fps);
+
+    Animator {
+      graph,
+      state: 0,
+      timer: Timer::from_seconds(frame_duration(fps), true),
+      is_finished: false,
+      params: AnimationParams::default(),
+    }
+  }
+
+  /// Switches to a state starting from its first frame
+  pub fn enter(&mut self, state: usize) {
+    self.state = state;
+    self.timer = Timer::from_seconds(frame_duration(self.graph.states[state].fps), true);
+    self.is_finished = false;
+  }
+
+  /// State to switch to, transitions of the graph win over the ones of the current state
+  pub fn next_state(&self) -> Option<usize> {
+    let current = &self.graph.states[self.state];
+
+    let transition = self
+      .graph
+      .transitions
+      .iter()
+      .chain(current.transitions.iter())
+      .find(|transition| transition.condition.check(&self.params, self.is_finished))?;
+
+    self
+      .graph
+      .index(&transition.to)
+      .filter(|state| *state != self.state)
+  }
+}
+
+fn frame_duration(fps: f32) -> f32 {
+  if fps > 0.0 {
+    1.0 / fps
+  } else {
+    f32::MAX
+  }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AnimationParams {
+  pub velocity: Vec3,
+  pub is_grabbing: bool,
+  pub is_climbing: bool,
+  pub is_dashing: bool,
+}
+
+/// Named animation states and the conditions to move between them
+pub struct AnimationGraph {
+  pub states: Vec<AnimationState>,
+  /// Checked from any state before the transitions of the state itself
+  pub transitions: Vec<Transition>,
+}
+
+impl AnimationGraph {
+  pub fn index(&self, name: &str) -> Option<usize> {
+    self.states.iter().position(|state| state.name == name)
+  }
+
+  pub fn player() -> AnimationGraph {
+    let state = |name: &str, atlas: &str, mode: PlaybackMode, to_idle: Condition| AnimationState {
+      name: name.to_string(),
+      atlas: Some(atlas.to_string()),
+      fps: 10.0,
+      mode,
+      hold_when: None,
+      transitions: vec![Transition::new("idle", to_idle)],
+    };
+
+    let mut climb = state(
+      "climb",
+      "player_climb",
+      PlaybackMode::Loop,
+      Condition::Always,
+    );
+    // hanging on a vine or a ladder keeps the current frame
+    climb.hold_when = Some(Condition::All(vec![
+      Condition::Not(Box::new(Condition::Rising)),
+      Condition::Not(Box::new(Condition::Falling)),
+    ]));
+
+    AnimationGraph {
+      states: vec![
+        AnimationState {
+          transitions: vec![Transition::new("run", Condition::Moving)],
+          ..state("idle", "player_idle", PlaybackMode::Loop, Condition::Always)
+        },
+        state(
+          "run",
+          "player_run",
+          PlaybackMode::Loop,
+          Condition::Not(Box::new(Condition::Moving)),
+        ),
+        state("jump", "player_jump", PlaybackMode::Loop, Condition::Always),
+        state("land", "player_land", PlaybackMode::Loop, Condition::Always),
+        state(
+          "grab",
+          "player_grab",
+          PlaybackMode::HoldLast,
+          Condition::Always,
+        ),
+        climb,
+        state("dash", "player_dash", PlaybackMode::Loop, Condition::Always),
+      ],
+      transitions: vec![
+        Transition::new("dash", Condition::Dashing),
+        Transition::new("climb", Condition::Climbing),
+        Transition::new("grab", Condition::Grabbing),
+        Transition::new("land", Condition::Falling),
+        Transition::new("jump", Condition::Rising),
+      ],
+    }
+  }
+
+  /// Enemies walk through their frames only while moving
+  pub fn walker(fps: f32) -> AnimationGraph {
+    AnimationGraph {
+      states: vec![AnimationState {
+        hold_when: Some(Condition::Not(Box::new(Condition::Moving))),
+        ..AnimationState::looping("walk", fps)
+      }],
+      transitions: Vec::new(),
+    }
+  }
+
+  /// Single looping animation, e.g. a spinning coin
+  pub fn looping(fps: f32) -> AnimationGraph {
+    AnimationGraph {
+      states: vec![AnimationState::looping("loop", fps)],
+      transitions: Vec::new(),
+    }
+  }
+}
+
+pub struct AnimationState {
+  pub name: String,
+  /// Key of the atlas in `Sprites`, the entity keeps its atlas if none
+  pub atlas: Option<String>,
+  pub fps: f32,
+  pub mode: PlaybackMode,
+  /// Frames stop advancing while the condition holds
+  pub hold_when: Option<Condition>,
+  pub transitions: Vec<Transition>,
+}
+
+impl AnimationState {
+  pub fn looping(name: &str, fps: f32) -> AnimationState {
+    AnimationState {
+      name: name.to_string(),
+      atlas: None,
+      fps,
+      mode: PlaybackMode::Loop,
+      hold_when: None,
+      transitions: Vec::new(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaybackMode {
+  Loop,
+  /// Plays once and stops on the last frame, `Condition::Finished` holds afterwards
+  Once,
+  /// Plays once and keeps the last frame for as long as the state lasts
+  HoldLast,
+}
+
+pub struct Transition {
+  pub to: String,
+  pub condition: Condition,
+}
+
+impl Transition {
+  pub fn new(to: &str, condition: Condition) -> Transition {
+    Transition {
+      to: to.to_string(),
+      condition,
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub enum Condition {
+  Always,
+  Moving,
+  Rising,
+  Falling,
+  Grabbing,
+  Climbing,
+  Dashing,
+  /// One-shot animation reached its last frame
+  Finished,
+  Not(Box<Condition>),
+  All(Vec<Condition>),
+}
+
+impl Condition {
+  pub fn check(&self, params: &AnimationParams, is_finished: bool) -> bool {
+    match self {
+      Condition::Always => true,
+      Condition::Moving => params.velocity.x != 0.0,
+      Condition::Rising => params.velocity.y > 0.0,
+      Condition::Falling => params.velocity.y < 0.0,
+      Condition::Grabbing => params.is_grabbing,
+      Condition::Climbing => params.is_climbing,
+      Condition::Dashing => params.is_dashing,
+      Condition::Finished => is_finished,
+      Condition::Not(condition) => !condition.check(params, is_finished),
+      Condition::All(conditions) => conditions
+        .iter()
+        .all(|condition| condition.check(params, is_finished)),
+    }
+  }
+}
+
+/// One tile of a parallax layer, see `assets/background/parallax.ron`
 pub struct Background {
-  pub acceleration: f32,
+  /// How much the layer follows the camera, 1 stays on the screen and 0 moves with the world
+  pub factor: Vec2,
+  pub offset: Vec2,
+  /// Width of a tile, tiles of a layer are placed next to each other
+  pub width: f32,
+  pub tile: u32,
+}
+
+/// Area of a boss fight, the camera stays inside while the boss is alive
+pub struct Arena {
+  pub size: Vec2,
 }
 
 pub struct Block {
   pub size: Vec2,
+  pub surface: Surface,
+}
+
+/// Block destroyed by a dash or by jumping into it from below
+pub struct Breakable {
+  pub debris: Handle<ColorMaterial>,
+}
+
+/// Platform that falls a moment after the player stands on it and comes back later
+pub struct Crumbling {
+  pub size: Vec2,
+  pub surface: Surface,
+  pub origin: Vec3,
+  pub state: CrumbleState,
+  pub timer: Timer,
+  /// Vertical velocity while falling
+  pub velocity: f32,
+  pub debris: Handle<ColorMaterial>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrumbleState {
+  Solid,
+  Shaking,
+  Falling,
+  Fallen,
+}
+
+/// Boss goes through its phases as it loses health
+pub struct Boss {
+  pub phases: Vec<BossPhase>,
+  pub phase: usize,
+  pub attack_timer: Timer,
+  pub is_leaping: bool,
+}
+
+impl Boss {
+  pub fn new(phases: Vec<BossPhase>) -> Boss {
+    let interval = phases.first().map_or(1.0, |phase| phase.interval);
+
+    Boss {
+      phases,
+      phase: 0,
+      attack_timer: Timer::from_seconds(interval, false),
+      is_leaping: false,
+    }
+  }
+
+  /// Guardian at the end of the jungle: charges, then spits darts, then leaps
+  pub fn jungle_guardian() -> Boss {
+    Boss::new(vec![
+      BossPhase {
+        until_health: 10,
+        pattern: AttackPattern::Charge {
+          speed: BOSS_CHARGE_SPEED,
+        },
+        interval: 2.0,
+      },
+      BossPhase {
+        until_health: 6,
+        pattern: AttackPattern::Volley { darts: 3 },
+        interval: 1.5,
+      },
+      BossPhase {
+        until_health: 3,
+        pattern: AttackPattern::Leap {
+          speed: BOSS_LEAP_SPEED,
+          height: BOSS_LEAP_HEIGHT,
+        },
+        interval: 1.2,
+      },
+    ])
+  }
+}
+
+/// Phase is active once the boss health drops to `until_health` or below
+pub struct BossPhase {
+  pub until_health: u8,
+  pub pattern: AttackPattern,
+  pub interval: f32,
+}
+
+pub enum AttackPattern {
+  /// Runs at the player until hitting a wall
+  Charge { speed: f32 },
+  /// Shoots a fan of darts at the player
+  Volley { darts: u32 },
+  /// Jumps towards the player
+  Leap { speed: f32, height: f32 },
+}
+
+pub struct BossBar;
+
+pub struct BossBarFill;
+
+pub struct Climbable {
+  pub size: Vec2,
 }
 
 pub struct CheckPoint {
   pub size: Vec2,
+  pub is_exit: bool,
+  pub is_locked: bool,
+}
+
+/// Anything the player picks up to fill the inventory
+pub struct Collectible {
+  pub size: Vec2,
+  pub kind: CollectibleKind,
+  pub value: u32,
+  /// Tiled object id, identifies secret relics between runs
+  pub id: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CollectibleKind {
+  Coin,
+  Gem,
+  Key,
+  Relic,
+}
+
+impl CollectibleKind {
+  pub fn from_name(name: &str) -> Option<CollectibleKind> {
+    match name {
+      "coin" => Some(CollectibleKind::Coin),
+      "gem" => Some(CollectibleKind::Gem),
+      "key" => Some(CollectibleKind::Key),
+      "relic" => Some(CollectibleKind::Relic),
+      _ => None,
+    }
+  }
+}
+
+pub struct InventoryText;
+
+/// Follows the player, distances are in world pixels
+pub struct Camera {
+  /// Area around the focus point the player moves in without moving the camera
+  pub deadzone: Vec2,
+  /// How far the camera looks in the direction the player runs
+  pub look_ahead: f32,
+  /// How quickly the camera catches up with its target, per second
+  pub damping: f32,
+  /// Point the camera follows, none until the player is found
+  pub focus: Option<Vec2>,
+  /// Current zoom, eased towards the one requested by `CameraEffects`
+  pub zoom: f32,
+  /// Smoothed position before the shake and the snapping to whole pixels
+  pub position: Vec2,
+}
+
+impl Default for Camera {
+  fn default() -> Self {
+    Self {
+      deadzone: Vec2::new(CAMERA_DEADZONE_X, CAMERA_DEADZONE_Y),
+      look_ahead: CAMERA_LOOK_AHEAD,
+      damping: CAMERA_DAMPING,
+      focus: None,
+      zoom: 1.0,
+      position: Vec2::zero(),
+    }
+  }
+}
+
+/// Sprite showing the low resolution game view in the window
+pub struct Screen;
+
+#[derive(Clone)]
+pub struct Enemy {
+  pub size: Vec2,
+  pub velocity: Vec3,
+  pub behaviour: EnemyBehaviour,
+}
+
+#[derive(Clone)]
+pub enum EnemyBehaviour {
+  /// Walks along the ground and turns around at ledges and walls
+  Patrol { speed: f32 },
+  /// Flies back and forth along a sine wave around the spawn point
+  Flyer {
+    speed: f32,
+    range: f32,
+    amplitude: f32,
+    frequency: f32,
+    origin: Vec3,
+    time: f32,
+  },
+  /// Runs towards the player while it is close and in line of sight
+  Chaser { speed: f32, range: f32 },
+  /// Stays in place and shoots darts at the player in line of sight
+  Blowgun { range: f32, reload: Timer },
+  /// Moved by its `Boss` phases, only falls on its own
+  Boss,
+}
+
+/// Environmental force declared in Tiled, acts on the player while overlapping
+pub struct ForceField {
+  pub size: Vec2,
+  pub force: Force,
+}
+
+pub enum Force {
+  /// Launches the player with the given velocity on contact
+  Impulse(Vec3),
+  /// Reflects the player velocity, scaled by restitution
+  Bounce(f32),
+  /// Continuously pushes the player, units per second
+  Wind(Vec3),
+}
+
+pub struct Health {
+  pub current: u8,
+  pub max: u8,
+  /// Time after a hit during which no more damage is taken
+  pub invulnerability: Timer,
+}
+
+impl Health {
+  pub fn new(max: u8, invulnerability: f32) -> Health {
+    // nothing is immune when it appears
+    let mut timer = Timer::from_seconds(invulnerability, false);
+    timer.tick(invulnerability);
+
+    Health {
+      current: max,
+      max,
+      invulnerability: timer,
+    }
+  }
+}
+
+pub struct HealthText;
+
+pub struct OxygenText;
+
+pub struct TipText;
+
+/// Door, gate or platform that is solid while closed and opens on a link signal
+pub struct Gate {
+  pub size: Vec2,
+  pub is_open: bool,
+}
+
+/// Enemy waiting to be spawned by a link signal, e.g. from a trigger zone
+pub struct Spawner {
+  pub enemy: Enemy,
+  pub health: u8,
+  pub sprite_key: String,
+}
+
+/// Invisible zone firing game events as the player enters, stays in and leaves it
+pub struct Trigger {
+  pub size: Vec2,
+  pub on_enter: Vec<GameEvent>,
+  pub on_stay: Vec<GameEvent>,
+  pub on_exit: Vec<GameEvent>,
+  pub is_inside: bool,
+  /// Removed once the player leaves it for the first time
+  pub is_once: bool,
+}
+
+/// Door that needs a key from the inventory to open
+pub struct Lock;
+
+/// Tiled object id other objects refer to when sending signals
+pub struct Link {
+  pub id: u32,
+}
+
+/// Pressure plate or lever that signals its linked objects
+pub struct Switch {
+  pub size: Vec2,
+  pub kind: SwitchKind,
+  pub is_on: bool,
+  /// Tiled object ids of the objects to signal
+  pub targets: Vec<u32>,
+  /// Sends the opposite signal, e.g. to close a gate while a plate is pressed
+  pub is_inverted: bool,
+}
+
+impl Switch {
+  /// Signal for the targets in the current state
+  pub fn signal(&self) -> Signal {
+    Signal::from_state(self.is_on != self.is_inverted)
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SwitchKind {
+  /// Held down by the player or enemies standing on it
+  Plate,
+  /// Flipped by the player
+  Lever,
+}
+
+/// Restores health of the player on pick up
+pub struct Heal {
+  pub size: Vec2,
+  pub amount: u8,
+}
+
+/// Deals damage to the hurtboxes of the other team it overlaps
+pub struct Hitbox {
+  pub size: Vec2,
+  pub damage: u8,
+  pub knockback: Vec2,
+  pub team: Team,
 }
 
-pub struct Coin {
+/// Receives damage from the hitboxes of the other team
+pub struct Hurtbox {
   pub size: Vec2,
+  pub team: Team,
 }
 
-pub struct CoinsText;
+/// Hurts the player on touch, pits take all the health at once
+pub struct Hazard {
+  pub size: Vec2,
+  pub damage: u8,
+  pub kind: HazardKind,
+}
 
-pub struct Camera;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HazardKind {
+  Spikes,
+  Thorns,
+  Pit,
+}
+
+impl HazardKind {
+  pub fn from_name(name: &str) -> Option<HazardKind> {
+    match name {
+      "spikes" => Some(HazardKind::Spikes),
+      "thorns" => Some(HazardKind::Thorns),
+      "pit" => Some(HazardKind::Pit),
+      _ => None,
+    }
+  }
+}
 
 pub struct Hook {
   pub size: Vec2,
 }
 
+pub struct Particle {
+  pub velocity: Vec3,
+  pub lifetime: Timer,
+}
+
+/// Flies until it hits a block, a hurtbox of the other team or runs out of time
+pub struct Projectile {
+  pub velocity: Vec3,
+  pub gravity: f32,
+  pub lifetime: Timer,
+}
+
+/// Horizontal direction an entity looks at, its sprite is mirrored to face it too
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Facing {
+  Right,
+  Left,
+}
+
+impl Facing {
+  pub fn sign(&self) -> f32 {
+    match self {
+      Facing::Right => 1.0,
+      Facing::Left => -1.0,
+    }
+  }
+
+  pub fn forward(&self) -> Vec3 {
+    Vec3::new(self.sign(), 0.0, 0.0)
+  }
+}
+
+/// Places the pivot of the current frame at `anchor`, in sprite pixels relative to
+/// the parent, so frames of different sizes line up, e.g. on the feet of the player
+pub struct Pivot {
+  pub anchor: Vec2,
+}
+
 pub struct Player {
   pub size: Vec2,
   pub velocity: Vec3,
   pub initial_position: Vec3,
   pub is_grabbing: bool,
+  pub is_climbing: bool,
   pub is_in_air: bool,
+  pub is_dashing: bool,
+  pub dash_timer: Timer,
+  pub dash_cooldown: Timer,
+  pub air_dashes: u8,
+  pub surface: Surface,
+  pub is_swimming: bool,
+  pub oxygen: f32,
+  pub force: Vec3,
+  pub impulse: Vec3,
+  pub checkpoint: Vec3,
+  pub swipe_cooldown: Timer,
+  pub stones: u8,
+  /// Direction the player smashed into a block this frame, by a jump from below or a dash
+  pub smash: Option<Vec3>,
+}
+
+impl Player {
+  pub fn can_dash(&self) -> bool {
+    !self.is_dashing
+      && self.dash_cooldown.finished()
+      && !(self.is_in_air && self.air_dashes >= PLAYER_AIR_DASHES)
+  }
+
+  /// Adds a continuous force, it is applied and cleared on the next movement step
+  pub fn push(&mut self, force: Vec3) {
+    self.force += force;
+  }
+
+  /// Adds an instant change of velocity, applied on the next movement step
+  pub fn kick(&mut self, impulse: Vec3) {
+    self.impulse += impulse;
+  }
+}
+
+/// Material of a tile, read from the `surface` property of the tileset
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Surface {
+  Ground,
+  Mud,
+  Ice,
+  Moss,
+}
+
+impl Surface {
+  pub fn from_name(name: &str) -> Surface {
+    match name {
+      "mud" => Surface::Mud,
+      "ice" => Surface::Ice,
+      "moss" => Surface::Moss,
+      _ => Surface::Ground,
+    }
+  }
+
+  /// Multiplier of the player's maximum horizontal speed
+  pub fn speed(&self) -> f32 {
+    match self {
+      Surface::Mud => 0.5,
+      Surface::Ice => 1.25,
+      _ => 1.0,
+    }
+  }
+
+  /// Multiplier of how fast the player speeds up
+  pub fn traction(&self) -> f32 {
+    match self {
+      Surface::Mud => 0.6,
+      Surface::Ice => 0.2,
+      _ => 1.0,
+    }
+  }
+
+  /// Multiplier of how fast the player slows down without input
+  pub fn friction(&self) -> f32 {
+    match self {
+      Surface::Mud => 2.0,
+      Surface::Ice => 0.1,
+      _ => 1.0,
+    }
+  }
+
+  /// Part of the landing speed returned back as a bounce
+  pub fn bounce(&self) -> f32 {
+    match self {
+      Surface::Moss => 0.8,
+      _ => 0.0,
+    }
+  }
+}
+
+/// Stone lying around, the player picks it up to throw later
+pub struct Throwable {
+  pub size: Vec2,
+}
+
+/// Short lived hitbox of the player's melee attack
+pub struct Swipe {
+  pub lifetime: Timer,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Team {
+  Player,
+  Enemy,
 }
 
 pub struct TimerText;
+
+pub struct Water {
+  pub size: Vec2,
+}
@@ -1,5 +1,76 @@
 pub const WINDOW_WIDTH: f32 = 384.0;
 pub const WINDOW_HEIGHT: f32 = 216.0;
-pub const GRAVITY: f32 = 32.0;
-pub const PLAYER_HORIZONTAL_SPEED: f32 = 8.0;
-pub const PLAYER_INITIAL_VERTICAL_SPEED: f32 = 16.0;
+pub const GRAVITY: f32 = 8.0;
+pub const PLAYER_HORIZONTAL_SPEED: f32 = 2.0;
+pub const PLAYER_INITIAL_VERTICAL_SPEED: f32 = 4.0;
+pub const PLAYER_CLIMB_SPEED: f32 = 1.0;
+pub const PLAYER_DASH_SPEED: f32 = 6.0;
+pub const PLAYER_DASH_DURATION: f32 = 0.15;
+pub const PLAYER_DASH_COOLDOWN: f32 = 0.6;
+pub const PLAYER_AIR_DASHES: u8 = 1;
+pub const PLAYER_GROUND_ACCELERATION: f32 = 20.0;
+pub const PLAYER_GROUND_DECELERATION: f32 = 25.0;
+pub const PLAYER_AIR_ACCELERATION: f32 = 10.0;
+pub const PLAYER_AIR_DECELERATION: f32 = 5.0;
+pub const PLAYER_MIN_BOUNCE_SPEED: f32 = 1.0;
+pub const PLAYER_SWIM_STROKE_SPEED: f32 = 1.5;
+pub const PLAYER_OXYGEN: f32 = 8.0;
+pub const PLAYER_OXYGEN_REFILL: f32 = 4.0;
+pub const WATER_GRAVITY_FACTOR: f32 = 0.3;
+pub const WATER_BUOYANCY: f32 = 4.0;
+pub const WATER_DRAG: f32 = 3.0;
+pub const SPLASH_PARTICLES: u32 = 8;
+pub const PARTICLE_LIFETIME: f32 = 0.4;
+pub const PLAYER_HEALTH: u8 = 3;
+pub const PLAYER_INVULNERABILITY: f32 = 1.0;
+pub const INVULNERABILITY_BLINK: f32 = 0.1;
+pub const PLAYER_SWIPE_DURATION: f32 = 0.15;
+pub const PLAYER_SWIPE_COOLDOWN: f32 = 0.4;
+pub const PLAYER_SWIPE_DAMAGE: u8 = 1;
+pub const PLAYER_STOMP_DAMAGE: u8 = 1;
+pub const PLAYER_STOMP_BOUNCE: f32 = 2.5;
+pub const HAZARD_KNOCKBACK_X: f32 = 1.5;
+pub const HAZARD_KNOCKBACK_Y: f32 = 2.5;
+pub const ENEMY_PATROL_SPEED: f32 = 0.5;
+pub const ENEMY_FLYER_SPEED: f32 = 0.5;
+pub const ENEMY_CHASER_SPEED: f32 = 1.25;
+pub const BOSS_CHARGE_SPEED: f32 = 1.5;
+pub const BOSS_LEAP_SPEED: f32 = 1.25;
+pub const BOSS_LEAP_HEIGHT: f32 = 3.5;
+pub const ENEMY_SIGHT_STEP: f32 = 2.0;
+pub const ENEMY_HEALTH: u8 = 2;
+pub const ENEMY_INVULNERABILITY: f32 = 0.3;
+pub const ENEMY_DAMAGE: u8 = 1;
+pub const ENEMY_BLOWGUN_RANGE: f32 = 128.0;
+pub const ENEMY_BLOWGUN_RELOAD: f32 = 2.0;
+pub const PROJECTILE_LIFETIME: f32 = 2.0;
+pub const DART_SPEED: f32 = 2.0;
+pub const STONE_THROW_SPEED_X: f32 = 2.5;
+pub const STONE_THROW_SPEED_Y: f32 = 1.5;
+pub const STONE_DAMAGE: u8 = 1;
+pub const BOSS_HEALTH: u8 = 10;
+pub const BOSS_VOLLEY_SPREAD: f32 = 0.3;
+pub const RELICS_FILE: &str = "relics.txt";
+pub const LEVEL: &str = "level1";
+pub const TIP_DURATION: f32 = 3.0;
+pub const CRUMBLE_DELAY: f32 = 0.5;
+pub const CRUMBLE_RESPAWN: f32 = 3.0;
+pub const CRUMBLE_SHAKE: f32 = 1.0;
+pub const CRUMBLE_FALL: f32 = 0.6;
+pub const DEBRIS_PARTICLES: u32 = 6;
+pub const ENEMY_ANIMATION_FPS: f32 = 8.0;
+pub const SHEETS_FOLDER: &str = "sheets";
+pub const CAMERA_DEADZONE_X: f32 = 32.0;
+pub const CAMERA_DEADZONE_Y: f32 = 48.0;
+pub const CAMERA_LOOK_AHEAD: f32 = 48.0;
+pub const CAMERA_DAMPING: f32 = 6.0;
+pub const CAMERA_SHAKE_OFFSET: f32 = 6.0;
+pub const CAMERA_SHAKE_FREQUENCY: f32 = 25.0;
+pub const CAMERA_TRAUMA_DECAY: f32 = 1.5;
+pub const CAMERA_PAN_DURATION: f32 = 2.0;
+pub const LANDING_SHAKE_SPEED: f32 = 5.0;
+pub const LANDING_TRAUMA: f32 = 0.3;
+pub const DAMAGE_TRAUMA: f32 = 0.5;
+pub const BOSS_INTRO_ZOOM: f32 = 1.5;
+pub const BOSS_INTRO_DURATION: f32 = 1.5;
+pub const PARALLAX_FILE: &str = "background/parallax.ron";
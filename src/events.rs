@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+/// Asks to take health from an entity, sent by hazards and hitboxes
+pub struct DamageEvent {
+  pub target: Entity,
+  pub amount: u8,
+  pub knockback: Vec3,
+}
+
+/// Signal from one Tiled object to another, see `Link`
+pub struct LinkEvent {
+  pub target: u32,
+  pub signal: Signal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Signal {
+  On,
+  Off,
+  Toggle,
+}
+
+impl Signal {
+  pub fn from_state(is_on: bool) -> Signal {
+    if is_on {
+      Signal::On
+    } else {
+      Signal::Off
+    }
+  }
+
+  /// State of a linked object after it receives the signal
+  pub fn apply(&self, is_on: bool) -> bool {
+    match self {
+      Signal::On => true,
+      Signal::Off => false,
+      Signal::Toggle => !is_on,
+    }
+  }
+}
+
+/// Scripted reaction of the game, published by trigger zones
+#[derive(Debug, Clone)]
+pub enum GameEvent {
+  ShowTip(String),
+  PlayMusic(String),
+  LockCamera(Vec2, Vec2),
+  UnlockCamera,
+  Link(u32, Signal),
+  /// Shows the linked object, e.g. a distant door, for a while
+  PanCamera(u32),
+}
+
+impl GameEvent {
+  /// Parses an action written in Tiled like "tip:Press E", "music:music/boss.mp3",
+  /// "lock_camera", "unlock_camera", "pan:12", "on:12", "off:12", "toggle:12" or "spawn:12",
+  /// `bounds` is the area of the zone, used to lock the camera
+  pub fn parse(action: &str, bounds: (Vec2, Vec2)) -> Option<GameEvent> {
+    let mut parts = action.trim().splitn(2, ':');
+    let name = parts.next()?.trim();
+    let argument = parts.next().map(|argument| argument.trim());
+    let target = argument.and_then(|argument| argument.parse().ok());
+
+    match (name, argument, target) {
+      ("tip", Some(text), _) => Some(GameEvent::ShowTip(text.to_string())),
+      ("music", Some(path), _) => Some(GameEvent::PlayMusic(path.to_string())),
+      ("lock_camera", _, _) => Some(GameEvent::LockCamera(bounds.0, bounds.1)),
+      ("unlock_camera", _, _) => Some(GameEvent::UnlockCamera),
+      ("pan", _, Some(id)) => Some(GameEvent::PanCamera(id)),
+      ("on", _, Some(id)) | ("spawn", _, Some(id)) => Some(GameEvent::Link(id, Signal::On)),
+      ("off", _, Some(id)) => Some(GameEvent::Link(id, Signal::Off)),
+      ("toggle", _, Some(id)) => Some(GameEvent::Link(id, Signal::Toggle)),
+      _ => None,
+    }
+  }
+}
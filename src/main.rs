@@ -1,15 +1,26 @@
 extern crate tiled;
 
+mod assets;
 mod components;
 mod constants;
+mod events;
+mod render;
 mod resources;
 mod systems;
 mod utils;
 
+use assets::{Aseprite, AsepriteLoader, SpriteSheet, SpriteSheetLoader};
 use bevy::{prelude::*, render::pass::ClearColor};
 use constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
-use resources::{GameState, Options, Sprites};
-use systems::{action, animation, gameover, movement, player, world};
+use events::{DamageEvent, GameEvent, LinkEvent};
+use resources::{
+  CameraEffects, CameraLock, GameState, Inventory, Options, Relics, Splash, Sprites, Tip,
+};
+use systems::{
+  action, animation, blocks, boss, camera, combat, damage, display, enemies, facing, forces,
+  game_events, gameover, graph, links, load_sheets, movement, parallax, particles, passes, pivots,
+  player, projectiles, sheets, sui, switches, triggers, ui, world,
+};
 
 fn main() {
   let options: Options = argh::from_env();
@@ -20,25 +31,57 @@ fn main() {
     title: "Jungle".to_string(),
     width: window_width,
     height: window_height,
+    resizable: true,
     ..Default::default()
   };
 
-  let sprites = Sprites::new();
-
   App::build()
     .init_resource::<GameState>()
+    .init_resource::<CameraLock>()
+    .init_resource::<CameraEffects>()
+    .init_resource::<Inventory>()
+    .init_resource::<Tip>()
+    .init_resource::<Splash>()
+    .insert_resource(Relics::load())
     .insert_resource(window)
-    .insert_resource(options)
-    .insert_resource(sprites)
+    .init_resource::<Sprites>()
     .insert_resource(ClearColor(Color::rgb(0.01, 0.01, 0.01)))
     .add_plugins(DefaultPlugins)
+    .add_asset::<SpriteSheet>()
+    .init_asset_loader::<SpriteSheetLoader>()
+    .add_asset::<Aseprite>()
+    .init_asset_loader::<AsepriteLoader>()
+    .add_event::<DamageEvent>()
+    .add_event::<LinkEvent>()
+    .add_event::<GameEvent>()
+    .add_startup_system(graph.system())
+    .add_startup_system(load_sheets.system())
     .add_startup_system(player.system())
     .add_startup_system(world.system())
-    // .add_startup_system(sui.system())
-    .add_system(movement.system())
+    .add_startup_system(sui.system())
+    .add_system(sheets.system())
+    .add_system(forces.system())
+    .add_system(movement.system().label("movement"))
+    .add_system(blocks.system().after("movement"))
     .add_system(action.system())
-    .add_system(animation.system())
+    .add_system(triggers.system())
+    .add_system(game_events.system())
+    .add_system(switches.system())
+    .add_system(links.system())
+    .add_system(enemies.system())
+    .add_system(boss.system())
+    .add_system(combat.system())
+    .add_system(projectiles.system())
+    .add_system(damage.system())
+    .add_system(facing.system().label("facing"))
+    .add_system(animation.system().label("animation"))
+    .add_system(pivots.system().after("animation").after("facing"))
     .add_system(gameover.system())
-    // .add_system(ui.system())
+    .add_system(camera.system().label("camera"))
+    .add_system(parallax.system().after("camera"))
+    .add_system(display.system().after("camera"))
+    .add_system(particles.system())
+    .add_system(ui.system())
+    .add_system_to_stage(CoreStage::PostUpdate, passes.system())
     .run();
 }
@@ -0,0 +1,118 @@
+use bevy::{
+  prelude::*,
+  reflect::TypeUuid,
+  render::{
+    render_graph::{Node, ResourceSlotInfo, ResourceSlots},
+    renderer::{RenderContext, RenderResourceId, RenderResourceType},
+    texture::{
+      Extent3d, FilterMode, SamplerDescriptor, TextureDescriptor, TextureDimension, TextureFormat,
+      TextureUsage, SAMPLER_ASSET_INDEX, TEXTURE_ASSET_INDEX,
+    },
+  },
+};
+use std::borrow::Cow;
+
+use super::constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
+
+/// Texture the game is drawn into before it is upscaled to the window
+pub const RENDER_TARGET: HandleUntyped =
+  HandleUntyped::weak_from_u64(Texture::TYPE_UUID, 4210752250417734561);
+
+pub const GAME_CAMERA: &str = "GameCamera";
+
+pub mod node {
+  pub const GAME_CAMERA: &str = "game_camera";
+  pub const GAME_PASS: &str = "game_pass";
+  pub const RENDER_TARGET: &str = "render_target";
+}
+
+/// A component that indicates that an entity is drawn in the low resolution game pass
+#[derive(Default)]
+pub struct GamePass;
+
+/// Creates the WINDOW_WIDTH x WINDOW_HEIGHT color and depth textures of the game pass
+/// and shares the color one through `RENDER_TARGET`, so a sprite can show it
+#[derive(Default)]
+pub struct RenderTargetNode {
+  is_created: bool,
+}
+
+impl RenderTargetNode {
+  pub const COLOR: &'static str = "color";
+  pub const DEPTH: &'static str = "depth";
+}
+
+impl Node for RenderTargetNode {
+  fn output(&self) -> &[ResourceSlotInfo] {
+    static OUTPUT: &[ResourceSlotInfo] = &[
+      ResourceSlotInfo {
+        name: Cow::Borrowed(RenderTargetNode::COLOR),
+        resource_type: RenderResourceType::Texture,
+      },
+      ResourceSlotInfo {
+        name: Cow::Borrowed(RenderTargetNode::DEPTH),
+        resource_type: RenderResourceType::Texture,
+      },
+    ];
+    OUTPUT
+  }
+
+  fn update(
+    &mut self,
+    _world: &World,
+    render_context: &mut dyn RenderContext,
+    _input: &ResourceSlots,
+    output: &mut ResourceSlots,
+  ) {
+    if self.is_created {
+      return;
+    }
+
+    let size = Extent3d {
+      width: WINDOW_WIDTH as u32,
+      height: WINDOW_HEIGHT as u32,
+      depth: 1,
+    };
+    let render_resource_context = render_context.resources_mut();
+
+    let color = render_resource_context.create_texture(TextureDescriptor {
+      size,
+      mip_level_count: 1,
+      sample_count: 1,
+      dimension: TextureDimension::D2,
+      format: TextureFormat::default(),
+      usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::SAMPLED,
+    });
+    let depth = render_resource_context.create_texture(TextureDescriptor {
+      size,
+      mip_level_count: 1,
+      sample_count: 1,
+      dimension: TextureDimension::D2,
+      format: TextureFormat::Depth32Float,
+      usage: TextureUsage::OUTPUT_ATTACHMENT,
+    });
+
+    // texels are blown up into whole squares of pixels, never blended
+    let sampler = render_resource_context.create_sampler(&SamplerDescriptor {
+      mag_filter: FilterMode::Nearest,
+      min_filter: FilterMode::Nearest,
+      ..Default::default()
+    });
+
+    let handle = RENDER_TARGET.typed::<Texture>();
+    render_resource_context.set_asset_resource(
+      &handle,
+      RenderResourceId::Texture(color),
+      TEXTURE_ASSET_INDEX,
+    );
+    render_resource_context.set_asset_resource(
+      &handle,
+      RenderResourceId::Sampler(sampler),
+      SAMPLER_ASSET_INDEX,
+    );
+
+    output.set(RenderTargetNode::COLOR, RenderResourceId::Texture(color));
+    output.set(RenderTargetNode::DEPTH, RenderResourceId::Texture(depth));
+    self.is_created = true;
+  }
+}
@@ -1,6 +1,17 @@
 use argh::FromArgs;
-use bevy::prelude::*;
-use std::collections::HashMap;
+use bevy::{
+  asset::{Asset, HandleId},
+  prelude::*,
+  reflect::TypeUuid,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+
+use super::assets::{Aseprite, SpriteFrame, SpriteSheet};
+use super::components::CollectibleKind;
+use super::constants::{RELICS_FILE, TIP_DURATION};
 
 #[derive(FromArgs)]
 #[argh(description = "Jungle game settings")]
@@ -9,7 +20,7 @@ pub struct Options {
     option,
     default = "4",
     short = 's',
-    description = "scale of game window"
+    description = "initial scale of game window"
   )]
   pub scale: u32,
 }
@@ -23,7 +34,6 @@ pub enum Game {
 
 #[derive(Debug)]
 pub struct GameState {
-  pub coins: u8,
   pub timer: f32,
   pub game: Game,
 }
@@ -31,29 +41,244 @@ pub struct GameState {
 impl Default for GameState {
   fn default() -> Self {
     Self {
-      coins: 0,
       timer: 0.,
       game: Game::Paused,
     }
   }
 }
 
+/// Texture atlases of the sprite sheets, keyed by the sheet name
+#[derive(Default)]
 pub struct Sprites {
   library: HashMap<String, Handle<TextureAtlas>>,
+  frames: HashMap<String, Vec<SpriteFrame>>,
+  pivots: HashMap<String, Vec2>,
+  /// Files requested from the asset server that are not registered yet
+  sheets: Vec<(String, Handle<SpriteSheet>)>,
+  aseprites: Vec<(String, Handle<Aseprite>)>,
 }
 
 impl Sprites {
-  pub fn new() -> Sprites {
-    Sprites {
-      library: HashMap::new(),
-    }
-  }
-
   pub fn add(&mut self, key: String, atlas_handle: Handle<TextureAtlas>) {
     self.library.insert(key, atlas_handle);
   }
 
+  /// Atlas of a registered sheet
   pub fn get(&self, key: &str) -> Option<&Handle<TextureAtlas>> {
     self.library.get(key)
   }
+
+  /// Atlas handle of a sheet, also before the sheet is loaded, so entities can be
+  /// spawned with it right away and get their frames once it is registered
+  pub fn handle(&self, key: &str) -> Handle<TextureAtlas> {
+    self
+      .get(key)
+      .cloned()
+      .unwrap_or_else(|| Handle::weak(Sprites::handle_id(key)))
+  }
+
+  /// Every key maps to the same atlas handle id, no matter when it is asked for
+  pub fn handle_id(key: &str) -> HandleId {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+
+    HandleId::Id(TextureAtlas::TYPE_UUID, hasher.finish())
+  }
+
+  pub fn frame(&self, key: &str, index: u32) -> Option<&SpriteFrame> {
+    self.frames.get(key)?.get(index as usize)
+  }
+
+  /// Pivot of a frame of the atlas as a fraction of the frame size from its top left corner
+  pub fn pivot(&self, atlas_handle: &Handle<TextureAtlas>, index: u32) -> Option<Vec2> {
+    let key = self
+      .library
+      .iter()
+      .find(|(_key, handle)| handle.id == atlas_handle.id)
+      .map(|(key, _handle)| key)?;
+
+    let frame_pivot = self
+      .frame(key, index)
+      .and_then(|frame| frame.pivot)
+      .map(|(x, y)| Vec2::new(x, y));
+
+    frame_pivot.or_else(|| self.pivots.get(key).copied())
+  }
+
+  pub fn set_frames(&mut self, key: String, pivot: Vec2, frames: Vec<SpriteFrame>) {
+    self.pivots.insert(key.clone(), pivot);
+    self.frames.insert(key, frames);
+  }
+
+  pub fn request_sheet(&mut self, key: String, sheet_handle: Handle<SpriteSheet>) {
+    self.sheets.push((key, sheet_handle));
+  }
+
+  pub fn request_aseprite(&mut self, key: String, aseprite_handle: Handle<Aseprite>) {
+    self.aseprites.push((key, aseprite_handle));
+  }
+
+  /// Takes the requested sheets that finished loading
+  pub fn take_loaded_sheets(
+    &mut self,
+    sheets: &Assets<SpriteSheet>,
+  ) -> Vec<(String, Handle<SpriteSheet>)> {
+    take_loaded(&mut self.sheets, sheets)
+  }
+
+  /// Takes the requested Aseprite files that finished loading
+  pub fn take_loaded_aseprites(
+    &mut self,
+    aseprites: &Assets<Aseprite>,
+  ) -> Vec<(String, Handle<Aseprite>)> {
+    take_loaded(&mut self.aseprites, aseprites)
+  }
+}
+
+fn take_loaded<T: Asset>(
+  pending: &mut Vec<(String, Handle<T>)>,
+  assets: &Assets<T>,
+) -> Vec<(String, Handle<T>)> {
+  let (loaded, rest) = pending
+    .drain(..)
+    .partition(|(_key, handle)| assets.get(handle).is_some());
+
+  *pending = rest;
+  loaded
+}
+
+/// Area the camera has to stay within
+#[derive(Default)]
+pub struct CameraLock {
+  /// Set by trigger zones
+  pub bounds: Option<(Vec2, Vec2)>,
+  /// Set while fighting a boss, wins over trigger zones
+  pub arena: Option<(Vec2, Vec2)>,
+  /// Extents of the map, nothing outside of it is ever shown
+  pub level: Option<(Vec2, Vec2)>,
+}
+
+impl CameraLock {
+  pub fn area(&self) -> Option<(Vec2, Vec2)> {
+    self.arena.or(self.bounds)
+  }
+}
+
+/// Effects requested by the gameplay on top of the camera following the player
+#[derive(Default)]
+pub struct CameraEffects {
+  /// Strength of the screen shake from 0 to 1, decays over time
+  pub trauma: f32,
+  /// Zoom factor held until the timer finishes
+  pub zoom: Option<(f32, Timer)>,
+  /// Point the camera looks at instead of the player until the timer finishes
+  pub pan: Option<(Vec2, Timer)>,
+}
+
+impl CameraEffects {
+  pub fn shake(&mut self, trauma: f32) {
+    self.trauma = (self.trauma + trauma).min(1.0);
+  }
+
+  pub fn zoom(&mut self, factor: f32, duration: f32) {
+    self.zoom = Some((factor, Timer::from_seconds(duration, false)));
+  }
+
+  pub fn pan(&mut self, target: Vec2, duration: f32) {
+    self.pan = Some((target, Timer::from_seconds(duration, false)));
+  }
+}
+
+/// Material of the particles thrown up when the player gets into water
+#[derive(Default)]
+pub struct Splash {
+  pub material: Handle<ColorMaterial>,
+}
+
+/// Hint shown at the bottom of the screen for a while
+pub struct Tip {
+  pub text: String,
+  pub timer: Timer,
+}
+
+impl Default for Tip {
+  fn default() -> Self {
+    let mut timer = Timer::from_seconds(TIP_DURATION, false);
+    timer.tick(TIP_DURATION);
+
+    Self {
+      text: String::new(),
+      timer,
+    }
+  }
+}
+
+/// What the player collected in the current level and how much there is to find
+#[derive(Default)]
+pub struct Inventory {
+  collected: HashMap<CollectibleKind, u32>,
+  totals: HashMap<CollectibleKind, u32>,
+}
+
+impl Inventory {
+  pub fn add(&mut self, kind: CollectibleKind, value: u32) {
+    *self.collected.entry(kind).or_insert(0) += value;
+  }
+
+  /// Spends collected items, e.g. a key on a door, returns false if there are not enough
+  pub fn take(&mut self, kind: CollectibleKind, value: u32) -> bool {
+    match self.collected.get_mut(&kind) {
+      Some(count) if *count >= value => {
+        *count -= value;
+        true
+      }
+      _ => false,
+    }
+  }
+
+  pub fn add_total(&mut self, kind: CollectibleKind, value: u32) {
+    *self.totals.entry(kind).or_insert(0) += value;
+  }
+
+  pub fn count(&self, kind: CollectibleKind) -> u32 {
+    self.collected.get(&kind).copied().unwrap_or(0)
+  }
+
+  pub fn total(&self, kind: CollectibleKind) -> u32 {
+    self.totals.get(&kind).copied().unwrap_or(0)
+  }
+}
+
+/// Secret relics found across all runs, kept in a plain text file one per line
+pub struct Relics {
+  found: HashSet<String>,
+}
+
+impl Relics {
+  pub fn load() -> Relics {
+    let found = fs::read_to_string(RELICS_FILE)
+      .map(|content| content.lines().map(|line| line.to_string()).collect())
+      .unwrap_or_default();
+
+    Relics { found }
+  }
+
+  pub fn is_found(&self, key: &str) -> bool {
+    self.found.contains(key)
+  }
+
+  pub fn find(&mut self, key: String) {
+    if self.found.insert(key) {
+      let mut lines: Vec<&str> = self.found.iter().map(|key| key.as_str()).collect();
+      lines.sort();
+
+      if let Err(error) = fs::write(RELICS_FILE, lines.join("\n")) {
+        warn!("Failed to save relics: {}", error);
+      }
+    }
+  }
+
+  pub fn key(level: &str, id: u32) -> String {
+    format!("{}:{}", level, id)
+  }
 }
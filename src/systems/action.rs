@@ -1,37 +1,119 @@
 use bevy::prelude::*;
 
-use super::super::components::{CheckPoint, Coin, Player};
-use super::super::resources::{Game, GameState};
+use super::super::components::{
+  CheckPoint, Collectible, CollectibleKind, Hazard, HazardKind, Heal, Health, Player, Throwable,
+};
+use super::super::constants::{HAZARD_KNOCKBACK_X, HAZARD_KNOCKBACK_Y, LEVEL};
+use super::super::events::DamageEvent;
+use super::super::resources::{Game, GameState, Inventory, Relics};
 use super::super::utils::collide_aabb;
 
 pub fn action(
   commands: &mut Commands,
   mut state: ResMut<GameState>,
-  mut player_query: Query<(&Player, &Transform)>,
-  mut coin_query: Query<(Entity, &Coin, &Transform)>,
+  mut inventory: ResMut<Inventory>,
+  mut relics: ResMut<Relics>,
+  mut damage_events: ResMut<Events<DamageEvent>>,
+  mut player_query: Query<(Entity, &mut Player, &mut Health, &Transform)>,
+  mut collectible_query: Query<(Entity, &Collectible, &Transform)>,
   mut cp_query: Query<(&CheckPoint, &Transform)>,
+  mut hazard_query: Query<(&Hazard, &Transform)>,
+  mut heal_query: Query<(Entity, &Heal, &Transform)>,
+  mut throwable_query: Query<(Entity, &Throwable, &Transform)>,
 ) {
-  for (player, player_transform) in player_query.iter_mut() {
-    // Collecting coins
+  for (player_entity, mut player, mut health, player_transform) in player_query.iter_mut() {
+    // Collecting coins, gems, keys and relics
 
     let player_translate = player_transform.translation;
 
-    for (coin_entity, coin, coin_transform) in coin_query.iter_mut() {
-      let coin_translate = coin_transform.translation;
-      let collision = collide_aabb(player_translate, player.size, coin_translate, coin.size);
+    for (collectible_entity, collectible, collectible_transform) in collectible_query.iter_mut() {
+      let collectible_translate = collectible_transform.translation;
+      let collision = collide_aabb(
+        player_translate,
+        player.size,
+        collectible_translate,
+        collectible.size,
+      );
 
       if let Some(_collision) = collision {
-        state.coins += 1;
-        commands.despawn(coin_entity);
+        inventory.add(collectible.kind, collectible.value);
+
+        if collectible.kind == CollectibleKind::Relic {
+          relics.find(Relics::key(LEVEL, collectible.id));
+        }
+
+        commands.despawn(collectible_entity);
+      }
+    }
+
+    // Picking up fruits to heal, they stay around while health is full
+
+    for (heal_entity, heal, heal_transform) in heal_query.iter_mut() {
+      let heal_translate = heal_transform.translation;
+      let collision = collide_aabb(player_translate, player.size, heal_translate, heal.size);
+
+      if collision.is_some() && health.current < health.max {
+        health.current = health.current.saturating_add(heal.amount).min(health.max);
+        commands.despawn(heal_entity);
+      }
+    }
+
+    // Picking up stones to throw
+
+    for (throwable_entity, throwable, throwable_transform) in throwable_query.iter_mut() {
+      let throwable_translate = throwable_transform.translation;
+      let collision = collide_aabb(
+        player_translate,
+        player.size,
+        throwable_translate,
+        throwable.size,
+      );
+
+      if collision.is_some() {
+        player.stones = player.stones.saturating_add(1);
+        commands.despawn(throwable_entity);
       }
     }
 
+    // Touching hazards
+
+    for (hazard, hazard_transform) in hazard_query.iter_mut() {
+      let hazard_translate = hazard_transform.translation;
+      let collision = collide_aabb(player_translate, player.size, hazard_translate, hazard.size);
+
+      if collision.is_none() {
+        continue;
+      }
+
+      if let HazardKind::Pit = hazard.kind {
+        health.current = 0;
+        continue;
+      }
+
+      let direction = (player_translate.x - hazard_translate.x).signum();
+
+      damage_events.send(DamageEvent {
+        target: player_entity,
+        amount: hazard.damage,
+        knockback: Vec3::new(direction * HAZARD_KNOCKBACK_X, HAZARD_KNOCKBACK_Y, 0.0),
+      });
+    }
+
     for (cp, cp_transform) in cp_query.iter_mut() {
       let cp_translate = cp_transform.translation;
       let collision = collide_aabb(player_translate, player.size, cp_translate, cp.size);
 
       if let Some(_collision) = collision {
-        state.game = Game::Finished;
+        // respawn standing on the same ground as the checkpoint
+        player.checkpoint = Vec3::new(
+          cp_translate.x,
+          cp_translate.y + (player.size.y - cp.size.y) / 2.0,
+          player_translate.z,
+        );
+
+        if cp.is_exit && !cp.is_locked {
+          state.game = Game::Finished;
+        }
       }
     }
   }
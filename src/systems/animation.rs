@@ -1,92 +1,122 @@
 use bevy::prelude::*;
 
-use super::super::components::{Background, Camera, Coin, Player};
+use super::super::components::{AnimationParams, Animator, Enemy, Health, PlaybackMode, Player};
+use super::super::constants::INVULNERABILITY_BLINK;
 use super::super::resources::Sprites;
 
 pub fn animation(
   time: Res<Time>,
   sprites: Res<Sprites>,
-  window: Res<WindowDescriptor>,
   texture_atlases: Res<Assets<TextureAtlas>>,
-  mut player_query: Query<(
-    &Player,
-    &Transform,
-    &mut Timer,
-    &mut TextureAtlasSprite,
-    &mut Handle<TextureAtlas>,
+  mut player_query: Query<(&Player, &Children)>,
+  mut animator_queries: QuerySet<(
+    Query<(&Enemy, &mut Animator)>,
+    Query<(
+      &mut Animator,
+      &mut TextureAtlasSprite,
+      &mut Handle<TextureAtlas>,
+    )>,
   )>,
-  mut camera_query: Query<(&Camera, &mut Transform)>,
-  mut coin_query: Query<(
-    &Coin,
-    &mut Timer,
-    &mut TextureAtlasSprite,
-    &Handle<TextureAtlas>,
-  )>,
-  mut background_query: Query<(&Background, &mut Transform)>,
+  mut blink_query: Query<(Entity, &Health, Option<&Children>)>,
+  mut visible_query: Query<&mut Visible>,
 ) {
-  for (player, player_transform, mut timer, mut sprite, mut texture_atlas_handle) in
-    player_query.iter_mut()
-  {
-    timer.tick(time.delta_seconds());
-
-    if player.velocity.x != 0.0 {
-      if let Some(player_run) = sprites.get("player_run") {
-        texture_atlas_handle.id = player_run.id;
-      }
-    } else {
-      if let Some(player_idle) = sprites.get("player_idle") {
-        texture_atlas_handle.id = player_idle.id;
+  // the player sprite is a child of the player entity, see `Pivot`
+  for (player, children) in player_query.iter_mut() {
+    for child in children.iter() {
+      if let Ok((mut animator, _sprite, _texture_atlas_handle)) =
+        animator_queries.q1_mut().get_mut(*child)
+      {
+        animator.params = AnimationParams {
+          velocity: player.velocity,
+          is_grabbing: player.is_grabbing,
+          is_climbing: player.is_climbing,
+          is_dashing: player.is_dashing,
+        };
       }
     }
+  }
 
-    if player.velocity.y > 0.0 {
-      if let Some(player_jump) = sprites.get("player_jump") {
-        texture_atlas_handle.id = player_jump.id;
-      }
-    }
+  for (enemy, mut animator) in animator_queries.q0_mut().iter_mut() {
+    animator.params.velocity = enemy.velocity;
+  }
+
+  for (mut animator, mut sprite, mut texture_atlas_handle) in animator_queries.q1_mut().iter_mut() {
+    let animator = &mut *animator;
+
+    // follows transitions until the state settles, every new state starts from its first frame
+    for _ in 0..animator.graph.states.len() {
+      match animator.next_state() {
+        Some(state) => {
+          animator.enter(state);
+          sprite.index = 0;
 
-    if player.velocity.y < 0.0 {
-      if let Some(player_land) = sprites.get("player_land") {
-        texture_atlas_handle.id = player_land.id;
+          let atlas = animator.graph.states[state].atlas.as_ref();
+          if let Some(atlas_handle) = atlas.and_then(|atlas| sprites.get(atlas)) {
+            texture_atlas_handle.id = atlas_handle.id;
+          }
+
+          if let Some(duration) = frame_duration(&sprites, atlas, sprite.index) {
+            animator.timer.set_duration(duration);
+          }
+        }
+        None => break,
       }
     }
 
-    if player.is_grabbing {
-      if let Some(player_grab) = sprites.get("player_grab") {
-        texture_atlas_handle.id = player_grab.id;
-      }
+    animator.timer.tick(time.delta_seconds());
+
+    let state = &animator.graph.states[animator.state];
+    let is_held = state.hold_when.as_ref().map_or(false, |condition| {
+      condition.check(&animator.params, animator.is_finished)
+    });
+
+    if !animator.timer.finished() || is_held {
+      continue;
     }
 
-    if timer.finished() {
-      let texture_atlas = texture_atlases.get(texture_atlas_handle.clone()).unwrap();
-      if !(player.is_grabbing && sprite.index == 5) {
-        sprite.index = ((sprite.index as usize + 1) % texture_atlas.len()) as u32;
+    let frames = match texture_atlases.get(texture_atlas_handle.clone()) {
+      Some(texture_atlas) => texture_atlas.len() as u32,
+      None => continue,
+    };
+
+    match state.mode {
+      PlaybackMode::Loop => {
+        sprite.index = (sprite.index + 1) % frames;
+      }
+      PlaybackMode::Once | PlaybackMode::HoldLast => {
+        if sprite.index + 1 < frames {
+          sprite.index += 1;
+        } else if state.mode == PlaybackMode::Once {
+          animator.is_finished = true;
+        }
       }
     }
 
-    for (_camera, mut camera_transform) in camera_query.iter_mut() {
-      camera_transform.translation.x = player_transform.translation.x;
+    if let Some(duration) = frame_duration(&sprites, state.atlas.as_ref(), sprite.index) {
+      animator.timer.set_duration(duration);
     }
+  }
 
-    for (background, mut background_transform) in background_query.iter_mut() {
-      let player_translation = player_transform.translation;
-      let background_translation = background_transform.translation;
+  // blinking while invulnerable after a hit
+  for (entity, health, children) in blink_query.iter_mut() {
+    let is_visible = health.invulnerability.finished()
+      || (health.invulnerability.elapsed() / INVULNERABILITY_BLINK) as u32 % 2 == 1;
 
-      background_transform.translation.x += player.velocity.x * background.acceleration;
+    // sprites may be children of the entity with health
+    let mut sprite_entities = vec![entity];
+    if let Some(children) = children {
+      sprite_entities.extend(children.iter());
+    }
 
-      if player_translation.x - background_translation.x > window.width as f32 {
-        background_transform.translation.x += 2.0 * window.width as f32;
-      } else if player_translation.x - background_translation.x < -(window.width as f32) {
-        background_transform.translation.x -= 2.0 * window.width as f32;
+    for sprite_entity in sprite_entities {
+      if let Ok(mut visible) = visible_query.get_mut(sprite_entity) {
+        visible.is_visible = is_visible;
       }
     }
   }
+}
 
-  for (_coin, mut timer, mut sprite, texture_atlas_handle) in coin_query.iter_mut() {
-    timer.tick(time.delta_seconds());
-    if timer.finished() {
-      let texture_atlas = texture_atlases.get(texture_atlas_handle).unwrap();
-      sprite.index = ((sprite.index as usize + 1) % texture_atlas.textures.len()) as u32;
-    }
-  }
+/// Duration of a frame set in its sprite sheet, if any
+fn frame_duration(sprites: &Sprites, atlas: Option<&String>, index: u32) -> Option<f32> {
+  sprites.frame(atlas?, index)?.duration
 }
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+
+use super::super::components::{Block, Breakable, CrumbleState, Crumbling, Player};
+use super::super::constants::{
+  CRUMBLE_DELAY, CRUMBLE_FALL, CRUMBLE_RESPAWN, CRUMBLE_SHAKE, DEBRIS_PARTICLES, GRAVITY,
+  PARTICLE_LIFETIME,
+};
+use super::super::utils::collide_aabb;
+use super::particles::burst;
+
+pub fn blocks(
+  commands: &mut Commands,
+  time: Res<Time>,
+  mut player_query: Query<(&Player, &Transform)>,
+  mut breakable_query: Query<(Entity, &Breakable, &Block, &Transform)>,
+  mut crumbling_query: Query<(Entity, &mut Crumbling, &mut Transform, &mut Visible)>,
+) {
+  for (player, player_transform) in player_query.iter_mut() {
+    let player_translation = player_transform.translation;
+
+    // Breaking blocks the player smashed into
+
+    if let Some(direction) = player.smash {
+      for (block_entity, breakable, block, block_transform) in breakable_query.iter_mut() {
+        let collision = collide_aabb(
+          player_translation + direction,
+          player.size,
+          block_transform.translation,
+          block.size,
+        );
+
+        if collision.is_some() {
+          commands.despawn(block_entity);
+          burst(
+            commands,
+            breakable.debris.clone(),
+            block_transform.translation + Vec3::new(0.0, 0.0, 1.0),
+            DEBRIS_PARTICLES,
+            3.0,
+            Vec2::new(1.0, 2.0),
+            PARTICLE_LIFETIME * 2.0,
+          );
+        }
+      }
+    }
+
+    // Crumbling platforms shake under the player, fall and come back after a while
+
+    for (crumbling_entity, mut crumbling, mut crumbling_transform, mut visible) in
+      crumbling_query.iter_mut()
+    {
+      crumbling.timer.tick(time.delta_seconds());
+
+      let collision = collide_aabb(
+        player_translation - Vec3::new(0.0, 1.0, 0.0),
+        player.size,
+        crumbling.origin,
+        crumbling.size,
+      );
+
+      match crumbling.state {
+        CrumbleState::Solid => {
+          if collision.is_some() && !player.is_in_air {
+            crumbling.state = CrumbleState::Shaking;
+            crumbling.timer = Timer::from_seconds(CRUMBLE_DELAY, false);
+          }
+        }
+        CrumbleState::Shaking => {
+          let shake = (crumbling.timer.elapsed() * 60.0).sin() * CRUMBLE_SHAKE;
+          crumbling_transform.translation.x = crumbling.origin.x + shake;
+
+          if crumbling.timer.finished() {
+            crumbling.state = CrumbleState::Falling;
+            crumbling.timer = Timer::from_seconds(CRUMBLE_FALL, false);
+            crumbling.velocity = 0.0;
+            crumbling_transform.translation = crumbling.origin;
+
+            commands.remove_one::<Block>(crumbling_entity);
+            burst(
+              commands,
+              crumbling.debris.clone(),
+              crumbling.origin + Vec3::new(0.0, 0.0, 1.0),
+              DEBRIS_PARTICLES,
+              3.0,
+              Vec2::new(1.0, 2.0),
+              PARTICLE_LIFETIME * 2.0,
+            );
+          }
+        }
+        CrumbleState::Falling => {
+          crumbling.velocity -= GRAVITY * time.delta_seconds();
+          crumbling_transform.translation.y += crumbling.velocity;
+
+          if crumbling.timer.finished() {
+            crumbling.state = CrumbleState::Fallen;
+            crumbling.timer = Timer::from_seconds(CRUMBLE_RESPAWN, false);
+            crumbling_transform.translation = crumbling.origin;
+            visible.is_visible = false;
+          }
+        }
+        CrumbleState::Fallen => {
+          // waits for the player to get out of the way before coming back
+          if crumbling.timer.finished() && collision.is_none() {
+            crumbling.state = CrumbleState::Solid;
+            visible.is_visible = true;
+
+            commands.insert_one(
+              crumbling_entity,
+              Block {
+                size: crumbling.size,
+                surface: crumbling.surface,
+              },
+            );
+          }
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+
+use super::super::components::{
+  Arena, AttackPattern, Boss, CheckPoint, Enemy, Health, Hitbox, Player, Team,
+};
+use super::super::constants::{
+  BOSS_INTRO_DURATION, BOSS_INTRO_ZOOM, BOSS_VOLLEY_SPREAD, DART_SPEED, ENEMY_DAMAGE,
+  HAZARD_KNOCKBACK_X, HAZARD_KNOCKBACK_Y,
+};
+use super::super::resources::{CameraEffects, CameraLock, Sprites};
+use super::super::utils::collide_aabb;
+use super::projectiles::spawn_projectile;
+
+pub fn boss(
+  commands: &mut Commands,
+  time: Res<Time>,
+  sprites: Res<Sprites>,
+  mut camera_lock: ResMut<CameraLock>,
+  mut camera_effects: ResMut<CameraEffects>,
+  mut boss_query: Query<(&mut Boss, &mut Enemy, &Health, &Transform)>,
+  mut player_query: Query<(&Player, &Transform)>,
+  mut arena_query: Query<(&Arena, &Transform)>,
+  mut cp_query: Query<&mut CheckPoint>,
+) {
+  let (player_translation, player_size) = match player_query.iter_mut().next() {
+    Some((player, player_transform)) => (player_transform.translation, player.size),
+    None => return,
+  };
+
+  let mut is_boss_alive = false;
+
+  for (mut boss, mut enemy, health, boss_transform) in boss_query.iter_mut() {
+    let boss = &mut *boss;
+
+    is_boss_alive = true;
+
+    // moving on to the next phase once the health drops low enough
+    while boss.phase + 1 < boss.phases.len()
+      && health.current <= boss.phases[boss.phase + 1].until_health
+    {
+      boss.phase += 1;
+      boss.attack_timer = Timer::from_seconds(boss.phases[boss.phase].interval, false);
+    }
+
+    // the boss stops where a leap lands, the ground clears the vertical velocity
+    if boss.is_leaping && enemy.velocity.y == 0.0 {
+      boss.is_leaping = false;
+      enemy.velocity.x = 0.0;
+    }
+
+    boss.attack_timer.tick(time.delta_seconds());
+
+    if !boss.attack_timer.finished() {
+      continue;
+    }
+
+    boss.attack_timer.reset();
+
+    let boss_translation = boss_transform.translation;
+    let distance = player_translation - boss_translation;
+    let direction = distance.x.signum();
+
+    match boss.phases[boss.phase].pattern {
+      AttackPattern::Charge { speed } => {
+        enemy.velocity.x = direction * speed;
+      }
+      AttackPattern::Volley { darts } => {
+        if let Some(dart_atlas_handle) = sprites.get("projectile_dart") {
+          let aim = distance.y.atan2(distance.x);
+
+          for i in 0..darts {
+            let angle = aim + BOSS_VOLLEY_SPREAD * (i as f32 - (darts - 1) as f32 / 2.0);
+
+            spawn_projectile(
+              commands,
+              dart_atlas_handle.clone(),
+              boss_translation,
+              Vec3::new(angle.cos(), angle.sin(), 0.0) * DART_SPEED,
+              0.0,
+              Hitbox {
+                size: enemy.size / 6.0,
+                damage: ENEMY_DAMAGE,
+                knockback: Vec2::new(HAZARD_KNOCKBACK_X / 2.0, HAZARD_KNOCKBACK_Y / 2.0),
+                team: Team::Enemy,
+              },
+            );
+          }
+        }
+      }
+      AttackPattern::Leap { speed, height } => {
+        enemy.velocity.x = direction * speed;
+        enemy.velocity.y = height;
+        boss.is_leaping = true;
+      }
+    }
+  }
+
+  // the arena holds the camera while the player is inside and the boss is alive
+
+  let was_locked = camera_lock.arena.is_some();
+  camera_lock.arena = None;
+
+  if is_boss_alive {
+    for (arena, arena_transform) in arena_query.iter_mut() {
+      let arena_translation = arena_transform.translation;
+      let collision = collide_aabb(
+        player_translation,
+        player_size,
+        arena_translation,
+        arena.size,
+      );
+
+      if collision.is_some() {
+        let center = arena_translation.truncate();
+        camera_lock.arena = Some((center - arena.size / 2.0, center + arena.size / 2.0));
+      }
+    }
+
+    // the boss is introduced by a short zoom when the player enters the arena
+    if !was_locked && camera_lock.arena.is_some() {
+      camera_effects.zoom(BOSS_INTRO_ZOOM, BOSS_INTRO_DURATION);
+    }
+  } else {
+    for mut cp in cp_query.iter_mut() {
+      cp.is_locked = false;
+    }
+  }
+}
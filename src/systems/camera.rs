@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+use super::super::components::{Camera, Player};
+use super::super::constants::{
+  CAMERA_SHAKE_FREQUENCY, CAMERA_SHAKE_OFFSET, CAMERA_TRAUMA_DECAY, PLAYER_HORIZONTAL_SPEED,
+  WINDOW_HEIGHT, WINDOW_WIDTH,
+};
+use super::super::resources::{CameraEffects, CameraLock};
+
+pub fn camera(
+  time: Res<Time>,
+  camera_lock: Res<CameraLock>,
+  mut effects: ResMut<CameraEffects>,
+  mut player_query: Query<(&Player, &Transform)>,
+  mut camera_query: Query<(&mut Camera, &mut Transform)>,
+) {
+  let delta = time.delta_seconds();
+
+  if let Some((_factor, timer)) = effects.zoom.as_mut() {
+    if timer.tick(delta).finished() {
+      effects.zoom = None;
+    }
+  }
+
+  if let Some((_target, timer)) = effects.pan.as_mut() {
+    if timer.tick(delta).finished() {
+      effects.pan = None;
+    }
+  }
+
+  let trauma = effects.trauma;
+  effects.trauma = (trauma - CAMERA_TRAUMA_DECAY * delta).max(0.0);
+
+  for (player, player_transform) in player_query.iter_mut() {
+    let player_position = player_transform.translation.truncate();
+
+    for (mut camera, mut camera_transform) in camera_query.iter_mut() {
+      let half_deadzone = camera.deadzone / 2.0;
+      let is_first_frame = camera.focus.is_none();
+      let mut focus = camera.focus.unwrap_or(player_position);
+
+      // the focus point is dragged along by the edges of the deadzone
+      focus.x = focus
+        .x
+        .max(player_position.x - half_deadzone.x)
+        .min(player_position.x + half_deadzone.x);
+
+      // jumps do not shake the camera, it catches up vertically once the player
+      // stands or climbs somewhere, or leaves the deadzone
+      if !player.is_in_air || player.is_climbing {
+        focus.y = player_position.y;
+      } else {
+        focus.y = focus
+          .y
+          .max(player_position.y - half_deadzone.y)
+          .min(player_position.y + half_deadzone.y);
+      }
+
+      camera.focus = Some(focus);
+
+      let run = (player.velocity.x / PLAYER_HORIZONTAL_SPEED)
+        .max(-1.0)
+        .min(1.0);
+      let mut target = match effects.pan {
+        Some((pan_target, _)) => pan_target,
+        None => focus + Vec2::new(run * camera.look_ahead, 0.0),
+      };
+
+      let t = 1.0 - (-camera.damping * delta).exp();
+
+      // zooming in shows a smaller part of the world
+      let zoom = effects.zoom.as_ref().map_or(1.0, |(factor, _)| *factor);
+      camera.zoom += (zoom - camera.zoom) * t;
+
+      let half_size = Vec2::new(WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0) / camera.zoom;
+
+      // boss arenas and trigger zones keep the camera inside their bounds,
+      // the map edges are never crossed
+      for (min, max) in camera_lock.area().iter().chain(camera_lock.level.iter()) {
+        target.x = clamp_axis(target.x, min.x, max.x, half_size.x);
+        target.y = clamp_axis(target.y, min.y, max.y, half_size.y);
+      }
+
+      if is_first_frame {
+        camera.position = target;
+      }
+
+      let position = camera.position;
+      camera.position = position + (target - position) * t;
+
+      // shake grows with the square of the trauma, so small hits stay subtle
+      let seconds = time.seconds_since_startup() as f32 * CAMERA_SHAKE_FREQUENCY;
+      let shake = Vec2::new(seconds.sin() * (1.7 * seconds).cos(), (1.3 * seconds).sin())
+        * trauma
+        * trauma
+        * CAMERA_SHAKE_OFFSET;
+
+      // snapping to whole pixels keeps the low resolution art crisp
+      camera_transform.translation.x = (camera.position.x + shake.x).round();
+      camera_transform.translation.y = (camera.position.y + shake.y).round();
+    }
+  }
+}
+
+/// Keeps the view of `half_size` inside `min..max`, centered if it does not fit
+fn clamp_axis(value: f32, min: f32, max: f32, half_size: f32) -> f32 {
+  if max - min > 2.0 * half_size {
+    value.max(min + half_size).min(max - half_size)
+  } else {
+    (min + max) / 2.0
+  }
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+
+use super::super::components::{Enemy, Facing, Health, Hitbox, Hurtbox, Player, Swipe, Team};
+use super::super::constants::{
+  DAMAGE_TRAUMA, GRAVITY, HAZARD_KNOCKBACK_X, HAZARD_KNOCKBACK_Y, PLAYER_STOMP_BOUNCE,
+  PLAYER_STOMP_DAMAGE, PLAYER_SWIPE_DAMAGE, PLAYER_SWIPE_DURATION, STONE_DAMAGE,
+  STONE_THROW_SPEED_X, STONE_THROW_SPEED_Y,
+};
+use super::super::events::DamageEvent;
+use super::super::resources::{CameraEffects, Sprites};
+use super::super::utils::collide_aabb;
+use super::projectiles::spawn_projectile;
+
+pub fn combat(
+  commands: &mut Commands,
+  time: Res<Time>,
+  keyboard_input: Res<Input<KeyCode>>,
+  sprites: Res<Sprites>,
+  mut damage_events: ResMut<Events<DamageEvent>>,
+  mut player_query: Query<(&mut Player, &Facing, &Transform)>,
+  mut enemy_query: Query<(Entity, &Enemy, &Transform)>,
+  mut swipe_query: Query<(Entity, &mut Swipe)>,
+  mut hitbox_query: Query<(Entity, &Hitbox, &Transform)>,
+  mut hurtbox_query: Query<(Entity, &Hurtbox, &Transform)>,
+) {
+  let mut stomped = Vec::new();
+
+  for (mut player, facing, player_transform) in player_query.iter_mut() {
+    let player_translation = player_transform.translation;
+
+    // Melee swipe in front of the player
+
+    player.swipe_cooldown.tick(time.delta_seconds());
+
+    if keyboard_input.just_pressed(KeyCode::X) && player.swipe_cooldown.finished() {
+      player.swipe_cooldown.reset();
+
+      let forward = facing.forward();
+      let swipe_translation = player_translation + Vec3::new(forward.x * player.size.x, 0.0, 0.0);
+
+      commands
+        .spawn((
+          Transform::from_translation(swipe_translation),
+          GlobalTransform::default(),
+        ))
+        .with(Swipe {
+          lifetime: Timer::from_seconds(PLAYER_SWIPE_DURATION, false),
+        })
+        .with(Hitbox {
+          size: Vec2::new(player.size.x, player.size.y * 0.6),
+          damage: PLAYER_SWIPE_DAMAGE,
+          knockback: Vec2::new(HAZARD_KNOCKBACK_X, HAZARD_KNOCKBACK_Y / 2.0),
+          team: Team::Player,
+        });
+    }
+
+    // Throwing collected stones
+
+    if keyboard_input.just_pressed(KeyCode::C) && player.stones > 0 {
+      if let Some(stone_atlas_handle) = sprites.get("projectile_stone") {
+        player.stones -= 1;
+
+        let forward = facing.forward();
+
+        spawn_projectile(
+          commands,
+          stone_atlas_handle.clone(),
+          player_translation,
+          Vec3::new(forward.x * STONE_THROW_SPEED_X, STONE_THROW_SPEED_Y, 0.0),
+          GRAVITY,
+          Hitbox {
+            size: player.size / 4.0,
+            damage: STONE_DAMAGE,
+            knockback: Vec2::new(HAZARD_KNOCKBACK_X / 2.0, HAZARD_KNOCKBACK_Y / 2.0),
+            team: Team::Player,
+          },
+        );
+      }
+    }
+
+    // Stomping enemies from above
+
+    for (enemy_entity, enemy, enemy_transform) in enemy_query.iter_mut() {
+      let enemy_translation = enemy_transform.translation;
+      let collision = collide_aabb(
+        player_translation,
+        player.size,
+        enemy_translation,
+        enemy.size,
+      );
+
+      let is_above = player_translation.y - player.size.y / 2.0 > enemy_translation.y;
+      let velocity = player.velocity + player.impulse;
+
+      if collision.is_some() && is_above && velocity.y < 0.0 {
+        stomped.push(enemy_entity);
+
+        damage_events.send(DamageEvent {
+          target: enemy_entity,
+          amount: PLAYER_STOMP_DAMAGE,
+          knockback: Vec3::new(0.0, 0.0, 0.0),
+        });
+
+        player.kick(Vec3::new(0.0, PLAYER_STOMP_BOUNCE - velocity.y, 0.0));
+      }
+    }
+  }
+
+  for (swipe_entity, mut swipe) in swipe_query.iter_mut() {
+    swipe.lifetime.tick(time.delta_seconds());
+
+    if swipe.lifetime.finished() {
+      commands.despawn(swipe_entity);
+    }
+  }
+
+  // Hitboxes hurt everything of the other team they touch
+
+  for (hitbox_entity, hitbox, hitbox_transform) in hitbox_query.iter_mut() {
+    let hitbox_translation = hitbox_transform.translation;
+
+    // the enemy being stomped can not hurt the player at the same time
+    if stomped.contains(&hitbox_entity) {
+      continue;
+    }
+
+    for (hurtbox_entity, hurtbox, hurtbox_transform) in hurtbox_query.iter_mut() {
+      if hurtbox.team == hitbox.team || hurtbox_entity == hitbox_entity {
+        continue;
+      }
+
+      let hurtbox_translation = hurtbox_transform.translation;
+      let collision = collide_aabb(
+        hitbox_translation,
+        hitbox.size,
+        hurtbox_translation,
+        hurtbox.size,
+      );
+
+      if collision.is_some() {
+        let direction = (hurtbox_translation.x - hitbox_translation.x).signum();
+
+        damage_events.send(DamageEvent {
+          target: hurtbox_entity,
+          amount: hitbox.damage,
+          knockback: Vec3::new(direction * hitbox.knockback.x, hitbox.knockback.y, 0.0),
+        });
+      }
+    }
+  }
+}
+
+pub fn damage(
+  commands: &mut Commands,
+  time: Res<Time>,
+  mut camera_effects: ResMut<CameraEffects>,
+  mut damage_events: ResMut<Events<DamageEvent>>,
+  mut health_query: Query<(Entity, &mut Health, Option<&mut Player>, Option<&mut Enemy>)>,
+) {
+  for (_entity, mut health, _player, _enemy) in health_query.iter_mut() {
+    health.invulnerability.tick(time.delta_seconds());
+  }
+
+  for event in damage_events.drain() {
+    if let Ok((entity, mut health, player, enemy)) = health_query.get_mut(event.target) {
+      if !health.invulnerability.finished() || health.current == 0 {
+        continue;
+      }
+
+      if let Some(mut player) = player {
+        // dashing through danger is safe
+        if player.is_dashing {
+          continue;
+        }
+
+        let velocity = player.velocity;
+        player.kick(event.knockback - velocity);
+        camera_effects.shake(DAMAGE_TRAUMA);
+      }
+
+      health.current = health.current.saturating_sub(event.amount);
+      health.invulnerability.reset();
+
+      if let Some(mut enemy) = enemy {
+        enemy.velocity += event.knockback;
+
+        if health.current == 0 {
+          commands.despawn(entity);
+        }
+      }
+    }
+  }
+}
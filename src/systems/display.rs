@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+
+use super::super::components::{Camera, Screen};
+use super::super::constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
+
+pub fn display(
+  keyboard_input: Res<Input<KeyCode>>,
+  mut windows: ResMut<Windows>,
+  mut camera_query: Query<(&Camera, &mut Transform), Without<Screen>>,
+  mut screen_query: Query<&mut Transform, With<Screen>>,
+) {
+  if keyboard_input.just_pressed(KeyCode::F11) {
+    if let Some(window) = windows.get_primary_mut() {
+      let mode = match window.mode() {
+        WindowMode::Windowed => WindowMode::BorderlessFullscreen,
+        _ => WindowMode::Windowed,
+      };
+
+      window.set_mode(mode);
+    }
+  }
+
+  let window = match windows.get_primary() {
+    Some(window) => window,
+    None => return,
+  };
+
+  // the texture is upscaled by the largest whole number of physical pixels per
+  // texel that fits the window, the rest of it stays clear
+  let width = window.physical_width() as f32;
+  let height = window.physical_height() as f32;
+  let scale_factor = window.scale_factor() as f32;
+  let pixel_scale = (width / WINDOW_WIDTH)
+    .min(height / WINDOW_HEIGHT)
+    .floor()
+    .max(1.0);
+
+  // projections span the window in logical pixels, the game camera squeezes its
+  // view of WINDOW_WIDTH x WINDOW_HEIGHT pixels back into the texture
+  for (camera, mut camera_transform) in camera_query.iter_mut() {
+    camera_transform.scale = Vec3::new(
+      WINDOW_WIDTH * scale_factor / (width * camera.zoom),
+      WINDOW_HEIGHT * scale_factor / (height * camera.zoom),
+      1.0,
+    );
+  }
+
+  for mut screen_transform in screen_query.iter_mut() {
+    let scale = pixel_scale / scale_factor;
+    screen_transform.scale = Vec3::new(scale, scale, 1.0);
+
+    // an odd size of the window would put the edges of the texels between pixels
+    screen_transform.translation.x = (width % 2.0) / 2.0 / scale_factor;
+    screen_transform.translation.y = (height % 2.0) / 2.0 / scale_factor;
+  }
+}
@@ -0,0 +1,214 @@
+use bevy::prelude::*;
+use std::sync::Arc;
+
+use super::super::components::{
+  AnimationGraph, Animator, Block, Enemy, EnemyBehaviour, Facing, Health, Hitbox, Hurtbox, Player,
+  Team,
+};
+use super::super::constants::{
+  DART_SPEED, ENEMY_ANIMATION_FPS, ENEMY_DAMAGE, ENEMY_INVULNERABILITY, ENEMY_SIGHT_STEP, GRAVITY,
+  HAZARD_KNOCKBACK_X, HAZARD_KNOCKBACK_Y,
+};
+use super::super::resources::Sprites;
+use super::super::utils::{is_solid, move_and_collide};
+use super::projectiles::spawn_projectile;
+
+pub fn enemies(
+  commands: &mut Commands,
+  time: Res<Time>,
+  sprites: Res<Sprites>,
+  mut enemy_query: Query<(&mut Enemy, &mut Facing, &mut Transform)>,
+  mut block_query: Query<(&Block, &Transform)>,
+  mut player_query: Query<(&Player, &Transform)>,
+) {
+  let player_translation = match player_query.iter_mut().next() {
+    Some((_player, player_transform)) => player_transform.translation,
+    None => return,
+  };
+
+  for (mut enemy, mut facing, mut enemy_transform) in enemy_query.iter_mut() {
+    let enemy = &mut *enemy;
+    let enemy_translation = enemy_transform.translation;
+    let direction = enemy.velocity.x.signum();
+
+    match &mut enemy.behaviour {
+      EnemyBehaviour::Patrol { speed } => {
+        enemy.velocity.x = direction * *speed;
+        enemy.velocity.y -= GRAVITY * time.delta_seconds();
+      }
+      EnemyBehaviour::Flyer {
+        speed,
+        range,
+        amplitude,
+        frequency,
+        origin,
+        time: flight_time,
+      } => {
+        *flight_time += time.delta_seconds();
+
+        let height = origin.y + *amplitude * (*flight_time * *frequency).sin();
+
+        // turn back once too far from the spawn point
+        let is_out_of_range = (enemy_translation.x - origin.x).abs() > *range
+          && (enemy_translation.x - origin.x).signum() == direction;
+
+        let direction = if is_out_of_range {
+          -direction
+        } else {
+          direction
+        };
+
+        enemy.velocity.x = direction * *speed;
+        enemy.velocity.y = height - enemy_translation.y;
+      }
+      EnemyBehaviour::Chaser { speed, range } => {
+        let distance = player_translation - enemy_translation;
+        let sees_player = distance.length() < *range
+          && is_in_sight(enemy_translation, player_translation, &mut block_query);
+
+        enemy.velocity.x = if sees_player {
+          distance.x.signum() * *speed
+        } else {
+          0.0
+        };
+        enemy.velocity.y -= GRAVITY * time.delta_seconds();
+      }
+      EnemyBehaviour::Blowgun { range, reload } => {
+        let distance = player_translation - enemy_translation;
+
+        enemy.velocity.x = 0.0;
+        enemy.velocity.y -= GRAVITY * time.delta_seconds();
+        // always aims at the player
+        *facing = if distance.x < 0.0 {
+          Facing::Left
+        } else {
+          Facing::Right
+        };
+
+        reload.tick(time.delta_seconds());
+
+        let sees_player = distance.length() < *range
+          && is_in_sight(enemy_translation, player_translation, &mut block_query);
+
+        if sees_player && reload.finished() {
+          if let Some(dart_atlas_handle) = sprites.get("projectile_dart") {
+            reload.reset();
+
+            spawn_projectile(
+              commands,
+              dart_atlas_handle.clone(),
+              enemy_translation,
+              distance.normalize() * DART_SPEED,
+              0.0,
+              Hitbox {
+                size: enemy.size / 3.0,
+                damage: ENEMY_DAMAGE,
+                knockback: Vec2::new(HAZARD_KNOCKBACK_X / 2.0, HAZARD_KNOCKBACK_Y / 2.0),
+                team: Team::Enemy,
+              },
+            );
+          }
+        }
+      }
+      EnemyBehaviour::Boss => {
+        enemy.velocity.y -= GRAVITY * time.delta_seconds();
+      }
+    }
+
+    let is_walker = !matches!(enemy.behaviour, EnemyBehaviour::Flyer { .. });
+
+    let enemy_size = enemy.size;
+    let intended_velocity = enemy.velocity;
+    let (mut enemy_next_translation, contacts) = move_and_collide(
+      enemy_translation,
+      enemy_size,
+      &mut enemy.velocity,
+      block_query.iter_mut(),
+    );
+
+    // walkers look one step ahead so they never fall off a ledge
+    if is_walker && contacts.ground.is_some() && intended_velocity.x != 0.0 {
+      let ahead = Vec3::new(
+        enemy_next_translation.x + intended_velocity.x.signum() * (enemy_size.x / 2.0 + 1.0),
+        enemy_next_translation.y - enemy_size.y / 2.0 - 1.0,
+        0.0,
+      );
+
+      if !is_solid(ahead, block_query.iter_mut()) {
+        enemy_next_translation.x = enemy_translation.x;
+        enemy.velocity.x = 0.0;
+      }
+    }
+
+    // patrols and flyers turn around when they can not go further
+    let is_blocked = enemy.velocity.x == 0.0 && intended_velocity.x != 0.0;
+    let is_turning = matches!(
+      enemy.behaviour,
+      EnemyBehaviour::Patrol { .. } | EnemyBehaviour::Flyer { .. }
+    );
+
+    if is_turning {
+      enemy.velocity.x = if is_blocked {
+        -intended_velocity.x
+      } else {
+        intended_velocity.x
+      };
+    }
+
+    if enemy.velocity.x > 0.0 {
+      *facing = Facing::Right;
+    } else if enemy.velocity.x < 0.0 {
+      *facing = Facing::Left;
+    }
+
+    enemy_transform.translation = enemy_next_translation;
+  }
+}
+
+/// Checks points along the line between the two positions for blocks
+fn is_in_sight(from: Vec3, to: Vec3, block_query: &mut Query<(&Block, &Transform)>) -> bool {
+  let distance = to - from;
+  let steps = (distance.length() / ENEMY_SIGHT_STEP) as u32;
+
+  (1..steps).all(|step| {
+    let point = from + distance * (step as f32 / steps as f32);
+    !is_solid(point, block_query.iter_mut())
+  })
+}
+
+pub fn spawn_enemy(
+  commands: &mut Commands,
+  texture_atlas: Handle<TextureAtlas>,
+  translation: Vec3,
+  enemy: Enemy,
+  health: u8,
+) {
+  let size = enemy.size;
+
+  commands
+    .spawn(SpriteSheetBundle {
+      sprite: TextureAtlasSprite::new(0),
+      transform: Transform {
+        translation,
+        ..Default::default()
+      },
+      texture_atlas,
+      ..Default::default()
+    })
+    .with(enemy)
+    .with(Facing::Right)
+    .with(Health::new(health, ENEMY_INVULNERABILITY))
+    .with(Hurtbox {
+      size,
+      team: Team::Enemy,
+    })
+    .with(Hitbox {
+      size,
+      damage: ENEMY_DAMAGE,
+      knockback: Vec2::new(HAZARD_KNOCKBACK_X, HAZARD_KNOCKBACK_Y),
+      team: Team::Enemy,
+    })
+    .with(Animator::new(Arc::new(AnimationGraph::walker(
+      ENEMY_ANIMATION_FPS,
+    ))));
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+use super::super::components::Facing;
+
+pub fn facing(
+  mut facing_query: Query<(Entity, &Facing, Option<&Children>)>,
+  mut transform_query: Query<&mut Transform>,
+) {
+  for (entity, facing, children) in facing_query.iter_mut() {
+    let sign = facing.sign();
+
+    match children {
+      // children are mirrored around the parent: sprites and effects attached
+      // to it keep their place on the side the entity looks at
+      Some(children) => {
+        for child in children.iter() {
+          if let Ok(mut transform) = transform_query.get_mut(*child) {
+            if transform.scale.x.signum() != sign {
+              transform.scale.x = -transform.scale.x;
+              transform.translation.x = -transform.translation.x;
+            }
+          }
+        }
+      }
+      // entities drawing their own sprite, like enemies, are mirrored in place
+      None => {
+        if let Ok(mut transform) = transform_query.get_mut(entity) {
+          if transform.scale.x.signum() != sign {
+            transform.scale.x = -transform.scale.x;
+          }
+        }
+      }
+    }
+  }
+}
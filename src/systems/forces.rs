@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use super::super::components::{Force, ForceField, Player};
+use super::super::utils::collide_aabb;
+
+pub fn forces(
+  mut player_query: Query<(&mut Player, &Transform)>,
+  mut force_query: Query<(&ForceField, &Transform)>,
+) {
+  for (mut player, player_transform) in player_query.iter_mut() {
+    let player_translation = player_transform.translation;
+
+    for (field, field_transform) in force_query.iter_mut() {
+      let field_translation = field_transform.translation;
+      let collision = collide_aabb(
+        player_translation,
+        player.size,
+        field_translation,
+        field.size,
+      );
+
+      let collision = match collision {
+        Some(collision) => collision,
+        None => continue,
+      };
+
+      // velocity the player will have once pending impulses are applied,
+      // so a field does not kick twice before the movement step
+      let velocity = player.velocity + player.impulse;
+
+      match field.force {
+        Force::Impulse(impulse) => {
+          // a zero impulse has no direction to launch the player in
+          if impulse.length_squared() > 0.0 && velocity.dot(impulse) <= 0.0 {
+            // cancel the velocity along the impulse so every launch is the same
+            let direction = impulse.normalize();
+            player.kick(impulse - direction * velocity.dot(direction));
+          }
+        }
+        Force::Bounce(restitution) => {
+          if collision.x.abs() > collision.y.abs() {
+            if collision.y.signum() == velocity.y.signum() {
+              player.kick(Vec3::new(0.0, -velocity.y * (1.0 + restitution), 0.0));
+            }
+          } else if collision.x.signum() == velocity.x.signum() {
+            player.kick(Vec3::new(-velocity.x * (1.0 + restitution), 0.0, 0.0));
+          }
+        }
+        Force::Wind(force) => {
+          player.push(force);
+        }
+      }
+    }
+  }
+}
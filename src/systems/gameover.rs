@@ -1,17 +1,26 @@
 use bevy::prelude::*;
 
-use super::super::components::Player;
-use super::super::resources::{Game, GameState};
+use super::super::components::{Facing, Health, Player};
+use super::super::constants::PLAYER_OXYGEN;
+use super::super::resources::{CameraLock, Game, GameState};
 
 pub fn gameover(
   mut game_state: ResMut<GameState>,
-  mut player_query: Query<(&mut Player, &mut Transform)>,
+  camera_lock: Res<CameraLock>,
+  mut player_query: Query<(&mut Player, &mut Health, &mut Facing, &mut Transform)>,
 ) {
-  for (mut player, mut player_transform) in player_query.iter_mut() {
-    if player.velocity.y < -50.0 {
+  for (mut player, mut health, mut facing, mut player_transform) in player_query.iter_mut() {
+    let has_fallen = camera_lock
+      .level
+      .map_or(false, |(min, _max)| player_transform.translation.y < min.y);
+
+    if has_fallen || player.oxygen <= 0.0 || health.current == 0 {
       player.velocity = Vec3::new(0.0, 0.0, 0.0);
-      player_transform.translation = player.initial_position;
-      player_transform.rotation = Quat::from_rotation_y(0.0);
+      player.oxygen = PLAYER_OXYGEN;
+      health.current = health.max;
+      health.invulnerability.reset();
+      player_transform.translation = player.checkpoint;
+      *facing = Facing::Right;
     }
   }
 }
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use super::super::components::{Block, Gate, Link, Spawner, Surface, Switch, SwitchKind};
+use super::super::events::{LinkEvent, Signal};
+use super::super::resources::Sprites;
+use super::enemies::spawn_enemy;
+
+pub fn links(
+  commands: &mut Commands,
+  sprites: Res<Sprites>,
+  mut link_events: ResMut<Events<LinkEvent>>,
+  mut gate_query: Query<(Entity, &mut Gate, &Link, &mut Visible)>,
+  mut spawner_query: Query<(Entity, &Spawner, &Link, &Transform)>,
+  mut switch_query: Query<(&mut Switch, &Link, &mut TextureAtlasSprite)>,
+) {
+  let mut spawned = Vec::new();
+  let mut relayed = Vec::new();
+
+  for event in link_events.drain() {
+    // dormant enemies come to life once and leave no spawner behind
+    if event.signal != Signal::Off {
+      for (spawner_entity, spawner, link, spawner_transform) in spawner_query.iter_mut() {
+        if link.id != event.target || spawned.contains(&spawner_entity) {
+          continue;
+        }
+
+        spawn_enemy(
+          commands,
+          sprites.handle(&spawner.sprite_key),
+          spawner_transform.translation,
+          spawner.enemy.clone(),
+          spawner.health,
+        );
+
+        spawned.push(spawner_entity);
+        commands.despawn(spawner_entity);
+      }
+    }
+
+    for (gate_entity, mut gate, link, mut visible) in gate_query.iter_mut() {
+      if link.id != event.target {
+        continue;
+      }
+
+      let is_open = event.signal.apply(gate.is_open);
+
+      if is_open == gate.is_open {
+        continue;
+      }
+
+      gate.is_open = is_open;
+      visible.is_visible = !is_open;
+
+      // closed gates are solid like any other block
+      if is_open {
+        commands.remove_one::<Block>(gate_entity);
+      } else {
+        commands.insert_one(
+          gate_entity,
+          Block {
+            size: gate.size,
+            surface: Surface::Ground,
+          },
+        );
+      }
+    }
+
+    // levers are flipped like by the player and signal their own targets,
+    // plates follow whatever stands on them
+    for (mut switch, link, mut sprite) in switch_query.iter_mut() {
+      if link.id != event.target || switch.kind != SwitchKind::Lever {
+        continue;
+      }
+
+      let is_on = event.signal.apply(switch.is_on);
+
+      if is_on == switch.is_on {
+        continue;
+      }
+
+      switch.is_on = is_on;
+      sprite.index = is_on as u32;
+
+      for target in switch.targets.iter() {
+        relayed.push(LinkEvent {
+          target: *target,
+          signal: switch.signal(),
+        });
+      }
+    }
+  }
+
+  // relayed signals reach their targets on the next frame
+  for event in relayed {
+    link_events.send(event);
+  }
+}
@@ -1,15 +1,50 @@
 mod action;
 mod animation;
+mod blocks;
+mod boss;
+mod camera;
+mod combat;
+mod display;
+mod enemies;
+mod facing;
+mod forces;
 mod gameover;
+mod links;
 mod movement;
+mod parallax;
+mod particles;
+mod passes;
+mod pivots;
+mod projectiles;
+mod sheets;
 mod startup;
-// mod ui;
+mod switches;
+mod triggers;
+mod ui;
 
 pub use action::action;
 pub use animation::animation;
+pub use blocks::blocks;
+pub use boss::boss;
+pub use camera::camera;
+pub use combat::{combat, damage};
+pub use display::display;
+pub use enemies::enemies;
+pub use facing::facing;
+pub use forces::forces;
 pub use gameover::gameover;
+pub use links::links;
 pub use movement::movement;
+pub use parallax::parallax;
+pub use particles::particles;
+pub use passes::passes;
+pub use pivots::pivots;
+pub use projectiles::projectiles;
+pub use sheets::{load_sheets, sheets};
+pub use startup::graph::graph;
 pub use startup::player::player;
-// pub use startup::ui::ui as sui;
+pub use startup::ui::ui as sui;
 pub use startup::world::world;
-// pub use ui::ui;
+pub use switches::switches;
+pub use triggers::{game_events, triggers};
+pub use ui::ui;
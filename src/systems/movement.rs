@@ -1,38 +1,217 @@
 use bevy::prelude::*;
 
-use super::super::components::{Block, Hook, Player};
-use super::super::constants::{GRAVITY, PLAYER_HORIZONTAL_SPEED, PLAYER_INITIAL_VERTICAL_SPEED};
-use super::super::resources::{Game, GameState};
-use super::super::utils::collide_aabb;
+use super::super::components::{Block, Climbable, Facing, Hook, Player, Water};
+use super::super::constants::{
+  GRAVITY, LANDING_SHAKE_SPEED, LANDING_TRAUMA, PARTICLE_LIFETIME, PLAYER_AIR_ACCELERATION,
+  PLAYER_AIR_DECELERATION, PLAYER_CLIMB_SPEED, PLAYER_DASH_SPEED, PLAYER_GROUND_ACCELERATION,
+  PLAYER_GROUND_DECELERATION, PLAYER_HORIZONTAL_SPEED, PLAYER_INITIAL_VERTICAL_SPEED,
+  PLAYER_MIN_BOUNCE_SPEED, PLAYER_OXYGEN, PLAYER_OXYGEN_REFILL, PLAYER_SWIM_STROKE_SPEED,
+  SPLASH_PARTICLES, WATER_BUOYANCY, WATER_DRAG, WATER_GRAVITY_FACTOR,
+};
+use super::super::resources::{CameraEffects, Game, GameState, Splash};
+use super::super::utils::{approach, collide_aabb, move_and_collide};
+use super::particles::burst;
 
 pub fn movement(
+  commands: &mut Commands,
   time: Res<Time>,
   keyboard_input: Res<Input<KeyCode>>,
   mut state: ResMut<GameState>,
-  mut player_query: Query<(&mut Player, &mut Transform)>,
+  mut camera_effects: ResMut<CameraEffects>,
+  water_splash: Res<Splash>,
+  mut player_query: Query<(&mut Player, &mut Facing, &mut Transform)>,
   mut block_query: Query<(&Block, &Transform)>,
   mut hook_query: Query<(&Hook, &Transform)>,
+  mut climbable_query: Query<(&Climbable, &Transform)>,
+  mut water_query: Query<(&Water, &Transform)>,
 ) {
-  for (mut player, mut player_transform) in player_query.iter_mut() {
+  for (mut player, mut facing, mut player_transform) in player_query.iter_mut() {
     let mut key_pressed = false;
 
-    if keyboard_input.pressed(KeyCode::Right) {
+    // dash launches the player horizontally for a short time ignoring gravity
+    // and input, only a limited number of dashes is allowed before landing
+
+    player.dash_cooldown.tick(time.delta_seconds());
+
+    if keyboard_input.just_pressed(KeyCode::LShift) && player.can_dash() {
       key_pressed = true;
-      player.velocity.x = PLAYER_HORIZONTAL_SPEED;
-      player_transform.rotation = Quat::from_rotation_y(0.0);
+      player.is_dashing = true;
+      player.is_grabbing = false;
+      player.is_climbing = false;
+      player.dash_timer.reset();
+
+      if player.is_in_air {
+        player.air_dashes += 1;
+      }
     }
 
-    if keyboard_input.pressed(KeyCode::Left) {
-      key_pressed = true;
-      player.velocity.x = -PLAYER_HORIZONTAL_SPEED;
-      player_transform.rotation = Quat::from_rotation_y(std::f32::consts::PI);
+    if player.is_dashing {
+      player.dash_timer.tick(time.delta_seconds());
+
+      let direction = facing.sign();
+
+      player.velocity.x = direction * PLAYER_DASH_SPEED;
+      player.velocity.y = 0.0;
+
+      if player.dash_timer.finished() {
+        player.is_dashing = false;
+        player.velocity.x = direction * PLAYER_HORIZONTAL_SPEED;
+        player.dash_cooldown.reset();
+      }
+    }
+
+    // horizontal speed changes gradually, the rate depends on whether the
+    // player is in the air and on the surface material under the feet
+
+    if !player.is_dashing {
+      let mut direction = 0.0;
+
+      if keyboard_input.pressed(KeyCode::Right) {
+        key_pressed = true;
+        direction += 1.0;
+        *facing = Facing::Right;
+      }
+
+      if keyboard_input.pressed(KeyCode::Left) {
+        key_pressed = true;
+        direction -= 1.0;
+        *facing = Facing::Left;
+      }
+
+      let (acceleration, deceleration, max_speed) = if player.is_in_air {
+        (
+          PLAYER_AIR_ACCELERATION,
+          PLAYER_AIR_DECELERATION,
+          PLAYER_HORIZONTAL_SPEED,
+        )
+      } else {
+        (
+          PLAYER_GROUND_ACCELERATION * player.surface.traction(),
+          PLAYER_GROUND_DECELERATION * player.surface.friction(),
+          PLAYER_HORIZONTAL_SPEED * player.surface.speed(),
+        )
+      };
+
+      let target_speed = direction * max_speed;
+      let is_speeding_up = direction != 0.0
+        && player.velocity.x.signum() == direction
+        && player.velocity.x.abs() < max_speed;
+
+      let rate = if is_speeding_up || player.velocity.x == 0.0 {
+        acceleration
+      } else {
+        deceleration
+      };
+
+      player.velocity.x = approach(player.velocity.x, target_speed, rate * time.delta_seconds());
+    }
+
+    // water makes the player swim, it has to come up for air from time to time
+
+    let was_swimming = player.is_swimming;
+    let mut is_head_under_water = false;
+    let mut water_surface: Option<f32> = None;
+
+    player.is_swimming = false;
+
+    for (water, water_transform) in water_query.iter_mut() {
+      let water_translation = water_transform.translation;
+      let player_translation = player_transform.translation;
+      let collision = collide_aabb(
+        player_translation,
+        player.size,
+        water_translation,
+        water.size,
+      );
+
+      if collision.is_none() {
+        continue;
+      }
+
+      let water_top = water_translation.y + water.size.y / 2.0;
+
+      player.is_swimming = true;
+
+      if player_translation.y + player.size.y / 2.0 < water_top {
+        is_head_under_water = true;
+      }
+
+      water_surface = Some(water_surface.map_or(water_top, |surface| surface.max(water_top)));
+    }
+
+    // the player usually overlaps a few tiles of water, yet dives in once
+    if !was_swimming {
+      if let Some(surface) = water_surface {
+        let splash_translation = Vec3::new(player_transform.translation.x, surface, 16.0);
+        burst(
+          commands,
+          water_splash.material.clone(),
+          splash_translation,
+          SPLASH_PARTICLES,
+          2.0,
+          Vec2::new(0.75, 1.5),
+          PARTICLE_LIFETIME,
+        );
+      }
     }
 
-    if keyboard_input.just_released(KeyCode::Right) || keyboard_input.just_released(KeyCode::Left) {
-      player.velocity.x = 0.0;
+    if is_head_under_water {
+      player.oxygen -= time.delta_seconds();
+    } else {
+      player.oxygen =
+        (player.oxygen + PLAYER_OXYGEN_REFILL * time.delta_seconds()).min(PLAYER_OXYGEN);
     }
 
-    if keyboard_input.pressed(KeyCode::Up) {
+    if player.is_swimming {
+      player.is_in_air = true;
+      player.is_grabbing = false;
+    }
+
+    // climbing starts when Up or Down is pressed inside a ladder or vine area
+    // and lasts until the player leaves it
+
+    let mut can_climb = false;
+
+    for (climbable, climbable_transform) in climbable_query.iter_mut() {
+      let climbable_translation = climbable_transform.translation;
+      let collision = collide_aabb(
+        player_transform.translation,
+        player.size,
+        climbable_translation,
+        climbable.size,
+      );
+
+      if collision.is_some() {
+        can_climb = true;
+      }
+    }
+
+    if !can_climb || player.is_dashing {
+      player.is_climbing = false;
+    } else if keyboard_input.pressed(KeyCode::Up) || keyboard_input.pressed(KeyCode::Down) {
+      player.is_climbing = true;
+      player.is_grabbing = false;
+    }
+
+    if player.is_climbing {
+      player.velocity.y = 0.0;
+      player.is_in_air = false;
+
+      if keyboard_input.pressed(KeyCode::Up) {
+        key_pressed = true;
+        player.velocity.y = PLAYER_CLIMB_SPEED;
+      }
+
+      if keyboard_input.pressed(KeyCode::Down) {
+        key_pressed = true;
+        player.velocity.y = -PLAYER_CLIMB_SPEED;
+      }
+    } else if player.is_swimming {
+      if keyboard_input.just_pressed(KeyCode::Up) {
+        key_pressed = true;
+        player.velocity.y = PLAYER_SWIM_STROKE_SPEED;
+      }
+    } else if keyboard_input.pressed(KeyCode::Up) && !player.is_dashing {
       key_pressed = true;
       if !player.is_in_air || player.is_grabbing {
         player.velocity.y = PLAYER_INITIAL_VERTICAL_SPEED;
@@ -46,50 +225,84 @@ pub fn movement(
       }
     }
 
-    // player is constantly affected by gravity
-    if !player.is_grabbing {
-      player.velocity.y -= GRAVITY * time.delta_seconds();
+    // player is constantly affected by gravity unless holding on to something
+    if !player.is_grabbing && !player.is_climbing && !player.is_dashing {
+      if player.is_swimming {
+        player.velocity.y -= GRAVITY * WATER_GRAVITY_FACTOR * time.delta_seconds();
+
+        if is_head_under_water {
+          player.velocity.y += WATER_BUOYANCY * time.delta_seconds();
+        }
+
+        player.velocity /= 1.0 + WATER_DRAG * time.delta_seconds();
+      } else {
+        player.velocity.y -= GRAVITY * time.delta_seconds();
+      }
     }
 
-    let mut player_next_translation = player_transform.translation + player.velocity;
+    // forces and impulses collected from the environment since the last step
 
-    for (block, block_transform) in block_query.iter_mut() {
-      let block_translation = block_transform.translation;
-      let collision = collide_aabb(
-        player_next_translation,
-        player.size,
-        block_translation,
-        block.size,
-      );
+    let impulse = player.impulse;
 
-      if let Some(collision) = collision {
-        let collision_sign_y = collision.y.signum();
-        let velocity_sign_y = player.velocity.y.signum();
+    player.velocity += player.force * time.delta_seconds() + impulse;
+    player.force = Vec3::new(0.0, 0.0, 0.0);
+    player.impulse = Vec3::new(0.0, 0.0, 0.0);
 
-        if collision.x.abs() > collision.y.abs() && collision_sign_y == velocity_sign_y {
-          player_next_translation.y -= collision.y;
+    if impulse.y > 0.0 {
+      player.is_in_air = true;
+      player.is_grabbing = false;
+      player.is_climbing = false;
+    }
 
-          if player.velocity.y < 0.0 {
-            player.is_in_air = false;
-          }
+    let landing_speed = player.velocity.y;
+    let (mut player_next_translation, contacts) = move_and_collide(
+      player_transform.translation,
+      player.size,
+      &mut player.velocity,
+      block_query.iter_mut(),
+    );
 
-          player.velocity.y = 0.0;
-        } else {
-          player_next_translation.x -= collision.x;
+    if let Some(surface) = contacts.ground {
+      player.surface = surface;
+      player.air_dashes = 0;
 
-          player.velocity.x = 0.0;
-        }
+      if player.is_in_air && -landing_speed > LANDING_SHAKE_SPEED {
+        camera_effects.shake(LANDING_TRAUMA);
+      }
+
+      let bounce_speed = -landing_speed * surface.bounce();
+
+      if bounce_speed > PLAYER_MIN_BOUNCE_SPEED {
+        player.velocity.y = bounce_speed;
+        player.is_in_air = true;
+      } else {
+        player.is_in_air = false;
       }
     }
 
+    player.smash = if contacts.ceiling {
+      Some(Vec3::new(0.0, 1.0, 0.0))
+    } else if player.is_dashing && contacts.wall {
+      Some(facing.forward())
+    } else {
+      None
+    };
+
+    // hitting a wall ends the dash early
+    if player.is_dashing && player.velocity.x == 0.0 {
+      player.is_dashing = false;
+      player.dash_cooldown.reset();
+    }
+
     for (hook, hook_transform) in hook_query.iter_mut() {
       let hook_translation = hook_transform.translation;
       let player_translation = player_transform.translation;
       let collision = collide_aabb(player_translation, player.size, hook_translation, hook.size);
 
       if let Some(_collision) = collision {
-        if (player_translation.y - hook_translation.y).abs() < 8.0 && player.velocity.y < 0.0 {
+        if (player_translation.y - hook_translation.y).abs() < 2.0 && player.velocity.y < 0.0 {
           player.is_grabbing = true;
+          player.velocity.x = 0.0;
           player.velocity.y = 0.0;
           player_next_translation.y = hook_translation.y + hook.size.y / 2.0 - player.size.y / 2.0;
         }
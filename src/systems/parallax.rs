@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use super::super::components::{Background, Camera};
+
+pub fn parallax(
+  mut camera_query: Query<(&Camera, &Transform)>,
+  mut background_query: Query<(&Background, &mut Transform)>,
+) {
+  for (_camera, camera_transform) in camera_query.iter_mut() {
+    let camera_position = camera_transform.translation.truncate();
+
+    for (background, mut background_transform) in background_query.iter_mut() {
+      let origin = camera_position * background.factor + background.offset;
+
+      // tiles are laid next to each other starting from the one under the left
+      // half of the screen, so the layer never ends
+      let first_tile = ((camera_position.x - origin.x) / background.width).floor();
+
+      background_transform.translation.x =
+        origin.x + (first_tile + background.tile as f32) * background.width;
+      background_transform.translation.y = origin.y;
+    }
+  }
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+use super::super::components::Particle;
+use super::super::constants::GRAVITY;
+
+pub fn particles(
+  commands: &mut Commands,
+  time: Res<Time>,
+  mut particle_query: Query<(Entity, &mut Particle, &mut Transform)>,
+) {
+  for (particle_entity, mut particle, mut particle_transform) in particle_query.iter_mut() {
+    particle.lifetime.tick(time.delta_seconds());
+
+    if particle.lifetime.finished() {
+      commands.despawn(particle_entity);
+      continue;
+    }
+
+    particle.velocity.y -= GRAVITY * time.delta_seconds();
+    particle_transform.translation += particle.velocity;
+  }
+}
+
+/// Spawns `count` particles flying up in a fan from `translation`, `speed` is
+/// their horizontal and vertical speed at the sides and at the top of the fan
+pub fn burst(
+  commands: &mut Commands,
+  material: Handle<ColorMaterial>,
+  translation: Vec3,
+  count: u32,
+  size: f32,
+  speed: Vec2,
+  lifetime: f32,
+) {
+  for i in 0..count {
+    let angle = std::f32::consts::PI * (i as f32 + 0.5) / count as f32;
+
+    commands
+      .spawn(SpriteBundle {
+        material: material.clone(),
+        sprite: Sprite::new(Vec2::new(size, size)),
+        visible: Visible {
+          is_transparent: true,
+          ..Default::default()
+        },
+        transform: Transform {
+          translation,
+          ..Default::default()
+        },
+        ..Default::default()
+      })
+      .with(Particle {
+        velocity: Vec3::new(angle.cos() * speed.x, angle.sin() * speed.y, 0.0),
+        lifetime: Timer::from_seconds(lifetime, false),
+      });
+  }
+}
@@ -0,0 +1,13 @@
+use bevy::{prelude::*, render::render_graph::base::MainPass};
+
+use super::super::components::Screen;
+use super::super::render::GamePass;
+
+pub fn passes(commands: &mut Commands, query: Query<Entity, (Added<MainPass>, Without<Screen>)>) {
+  // new sprites are drawn into the low resolution texture, only the screen
+  // showing it stays in the main pass of the window
+  for entity in query.iter() {
+    commands.remove_one::<MainPass>(entity);
+    commands.insert_one(entity, GamePass);
+  }
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use super::super::components::Pivot;
+use super::super::resources::Sprites;
+
+pub fn pivots(
+  sprites: Res<Sprites>,
+  texture_atlases: Res<Assets<TextureAtlas>>,
+  mut pivot_query: Query<(
+    &Pivot,
+    &TextureAtlasSprite,
+    &Handle<TextureAtlas>,
+    &mut Transform,
+  )>,
+) {
+  for (pivot, sprite, texture_atlas_handle, mut transform) in pivot_query.iter_mut() {
+    let frame = texture_atlases
+      .get(texture_atlas_handle)
+      .and_then(|texture_atlas| texture_atlas.textures.get(sprite.index as usize));
+
+    let frame_size = match frame {
+      Some(frame) => frame.max - frame.min,
+      None => continue,
+    };
+
+    let fraction = sprites
+      .pivot(texture_atlas_handle, sprite.index)
+      .unwrap_or_else(|| Vec2::new(0.5, 0.5));
+
+    // sprites are drawn around their center, y of the pivot points down the frame
+    let offset = Vec2::new(
+      (fraction.x - 0.5) * frame_size.x,
+      (0.5 - fraction.y) * frame_size.y,
+    );
+
+    // mirrored sprites are flipped around their pivot, see `facing`
+    let flip = transform.scale.x.signum();
+
+    transform.translation.x = flip * (pivot.anchor.x - offset.x);
+    transform.translation.y = pivot.anchor.y - offset.y;
+  }
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+
+use super::super::components::{Block, Hitbox, Hurtbox, Projectile};
+use super::super::constants::PROJECTILE_LIFETIME;
+use super::super::events::DamageEvent;
+use super::super::utils::{collide_aabb, is_solid};
+
+pub fn projectiles(
+  commands: &mut Commands,
+  time: Res<Time>,
+  mut damage_events: ResMut<Events<DamageEvent>>,
+  mut projectile_query: Query<(Entity, &mut Projectile, &Hitbox, &mut Transform)>,
+  mut block_query: Query<(&Block, &Transform)>,
+  mut hurtbox_query: Query<(Entity, &Hurtbox, &Transform)>,
+) {
+  for (projectile_entity, mut projectile, hitbox, mut projectile_transform) in
+    projectile_query.iter_mut()
+  {
+    projectile.lifetime.tick(time.delta_seconds());
+
+    if projectile.lifetime.finished() {
+      commands.despawn(projectile_entity);
+      continue;
+    }
+
+    projectile.velocity.y -= projectile.gravity * time.delta_seconds();
+
+    let projectile_translation = projectile_transform.translation + projectile.velocity;
+
+    if is_solid(projectile_translation, block_query.iter_mut()) {
+      commands.despawn(projectile_entity);
+      continue;
+    }
+
+    // the projectile goes away before it reaches the target, so it deals
+    // the damage itself instead of leaving it to the hitbox check in combat
+    let target = hurtbox_query
+      .iter_mut()
+      .find(|(_hurtbox_entity, hurtbox, hurtbox_transform)| {
+        let collision = collide_aabb(
+          projectile_translation,
+          hitbox.size,
+          hurtbox_transform.translation,
+          hurtbox.size,
+        );
+
+        hurtbox.team != hitbox.team && collision.is_some()
+      });
+
+    if let Some((hurtbox_entity, _hurtbox, _hurtbox_transform)) = target {
+      let direction = projectile.velocity.x.signum();
+
+      damage_events.send(DamageEvent {
+        target: hurtbox_entity,
+        amount: hitbox.damage,
+        knockback: Vec3::new(direction * hitbox.knockback.x, hitbox.knockback.y, 0.0),
+      });
+
+      commands.despawn(projectile_entity);
+      continue;
+    }
+
+    let velocity = projectile.velocity;
+
+    projectile_transform.translation = projectile_translation;
+    projectile_transform.rotation = Quat::from_rotation_z(velocity.y.atan2(velocity.x));
+  }
+}
+
+pub fn spawn_projectile(
+  commands: &mut Commands,
+  texture_atlas: Handle<TextureAtlas>,
+  translation: Vec3,
+  velocity: Vec3,
+  gravity: f32,
+  hitbox: Hitbox,
+) {
+  commands
+    .spawn(SpriteSheetBundle {
+      sprite: TextureAtlasSprite::new(0),
+      transform: Transform {
+        translation,
+        rotation: Quat::from_rotation_z(velocity.y.atan2(velocity.x)),
+        ..Default::default()
+      },
+      texture_atlas,
+      ..Default::default()
+    })
+    .with(Projectile {
+      velocity,
+      gravity,
+      lifetime: Timer::from_seconds(PROJECTILE_LIFETIME, false),
+    })
+    .with(hitbox);
+}
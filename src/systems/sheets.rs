@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::Path;
+
+use bevy::{prelude::*, sprite::Rect as FrameRect};
+
+use super::super::assets::{Aseprite, SpriteFrame, SpriteSheet};
+use super::super::constants::SHEETS_FOLDER;
+use super::super::resources::Sprites;
+
+/// Requests every `.sheet` and Aseprite file of the sheets folder
+pub fn load_sheets(asset_server: Res<AssetServer>, mut sprites: ResMut<Sprites>) {
+  let entries = match fs::read_dir(Path::new("assets").join(SHEETS_FOLDER)) {
+    Ok(entries) => entries,
+    Err(error) => {
+      error!("Failed to read sprite sheets: {}", error);
+      return;
+    }
+  };
+
+  for entry in entries.filter_map(|entry| entry.ok()) {
+    let path = entry.path();
+    let key = match path.file_stem().and_then(|stem| stem.to_str()) {
+      Some(key) => key.to_string(),
+      None => continue,
+    };
+    let asset_path = format!(
+      "{}/{}",
+      SHEETS_FOLDER,
+      path.file_name().unwrap().to_string_lossy()
+    );
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+      Some("sheet") => sprites.request_sheet(key, asset_server.load(asset_path.as_str())),
+      Some("aseprite") | Some("ase") => {
+        sprites.request_aseprite(key, asset_server.load(asset_path.as_str()))
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Registers loaded sheets into `Sprites`, filling in the atlases entities already point at
+pub fn sheets(
+  asset_server: Res<AssetServer>,
+  mut sprites: ResMut<Sprites>,
+  sheets: Res<Assets<SpriteSheet>>,
+  aseprites: Res<Assets<Aseprite>>,
+  mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+  for (key, sheet_handle) in sprites.take_loaded_sheets(&sheets) {
+    let sheet = sheets.get(sheet_handle).unwrap();
+
+    let mut texture_atlas = TextureAtlas::new_empty(
+      asset_server.load(sheet.texture.as_str()),
+      Vec2::new(sheet.size.0, sheet.size.1),
+    );
+
+    for frame in sheet.frames.iter() {
+      let (x, y, width, height) = frame.rect;
+
+      texture_atlas.add_texture(FrameRect {
+        min: Vec2::new(x, y),
+        max: Vec2::new(x + width, y + height),
+      });
+    }
+
+    register(
+      &mut sprites,
+      &mut texture_atlases,
+      key,
+      texture_atlas,
+      Vec2::new(sheet.pivot.0, sheet.pivot.1),
+      sheet.frames.clone(),
+    );
+  }
+
+  // Aseprite files give a sheet per layer and tag, named `file_tag` or `file_layer_tag`
+  // when there are several layers, and `file` or `file_layer` for all the frames
+
+  for (key, aseprite_handle) in sprites.take_loaded_aseprites(&aseprites) {
+    let aseprite = aseprites.get(aseprite_handle).unwrap();
+    let (width, height) = aseprite.frame_size;
+    let all_frames: Vec<u32> = (0..aseprite.durations.len() as u32).collect();
+
+    let mut ranges = vec![(String::new(), &all_frames)];
+    for tag in aseprite.tags.iter() {
+      ranges.push((format!("_{}", tag.name), &tag.frames));
+    }
+
+    for layer in aseprite.layers.iter() {
+      let layer_key = if aseprite.layers.len() > 1 {
+        format!("{}_{}", key, layer.name)
+      } else {
+        key.clone()
+      };
+
+      for (suffix, frames) in ranges.iter() {
+        let mut texture_atlas = TextureAtlas::new_empty(
+          layer.texture.clone(),
+          Vec2::new(width * aseprite.durations.len() as f32, height),
+        );
+
+        let sprite_frames: Vec<SpriteFrame> = frames
+          .iter()
+          .map(|frame| {
+            let x = width * *frame as f32;
+
+            texture_atlas.add_texture(FrameRect {
+              min: Vec2::new(x, 0.0),
+              max: Vec2::new(x + width, height),
+            });
+
+            SpriteFrame {
+              rect: (x, 0.0, width, height),
+              duration: Some(aseprite.durations[*frame as usize]),
+              pivot: None,
+            }
+          })
+          .collect();
+
+        register(
+          &mut sprites,
+          &mut texture_atlases,
+          format!("{}{}", layer_key, suffix),
+          texture_atlas,
+          Vec2::new(0.5, 0.5),
+          sprite_frames,
+        );
+      }
+    }
+  }
+}
+
+fn register(
+  sprites: &mut Sprites,
+  texture_atlases: &mut Assets<TextureAtlas>,
+  key: String,
+  texture_atlas: TextureAtlas,
+  pivot: Vec2,
+  frames: Vec<SpriteFrame>,
+) {
+  let atlas_handle = texture_atlases.set(Sprites::handle_id(&key), texture_atlas);
+
+  sprites.set_frames(key.clone(), pivot, frames);
+  sprites.add(key, atlas_handle);
+}
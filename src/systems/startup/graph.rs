@@ -0,0 +1,78 @@
+use bevy::{
+  prelude::*,
+  render::{
+    camera::ActiveCameras,
+    pass::{
+      LoadOp, Operations, PassDescriptor, RenderPassColorAttachmentDescriptor,
+      RenderPassDepthStencilAttachmentDescriptor, TextureAttachment,
+    },
+    render_graph::{base, CameraNode, PassNode, RenderGraph},
+  },
+  sprite,
+};
+
+use super::super::super::render::{node, GamePass, RenderTargetNode, GAME_CAMERA};
+
+pub fn graph(mut graph: ResMut<RenderGraph>, mut active_cameras: ResMut<ActiveCameras>) {
+  let mut game_pass = PassNode::<&GamePass>::new(PassDescriptor {
+    color_attachments: vec![RenderPassColorAttachmentDescriptor {
+      attachment: TextureAttachment::Input("color_attachment".to_string()),
+      resolve_target: None,
+      ops: Operations {
+        load: LoadOp::Clear(Color::BLACK),
+        store: true,
+      },
+    }],
+    depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+      attachment: TextureAttachment::Input("depth".to_string()),
+      depth_ops: Some(Operations {
+        load: LoadOp::Clear(1.0),
+        store: true,
+      }),
+      stencil_ops: None,
+    }),
+    sample_count: 1,
+  });
+
+  game_pass.use_default_clear_color(0);
+  game_pass.add_camera(GAME_CAMERA);
+  active_cameras.add(GAME_CAMERA);
+
+  graph.add_node(node::GAME_PASS, game_pass);
+  graph.add_system_node(node::GAME_CAMERA, CameraNode::new(GAME_CAMERA));
+  graph.add_node(node::RENDER_TARGET, RenderTargetNode::default());
+
+  graph
+    .add_slot_edge(
+      node::RENDER_TARGET,
+      RenderTargetNode::COLOR,
+      node::GAME_PASS,
+      "color_attachment",
+    )
+    .unwrap();
+  graph
+    .add_slot_edge(
+      node::RENDER_TARGET,
+      RenderTargetNode::DEPTH,
+      node::GAME_PASS,
+      "depth",
+    )
+    .unwrap();
+
+  for input in [
+    node::GAME_CAMERA,
+    base::node::TEXTURE_COPY,
+    base::node::SHARED_BUFFERS,
+    sprite::node::COLOR_MATERIAL,
+    sprite::node::SPRITE,
+  ]
+  .iter()
+  {
+    graph.add_node_edge(*input, node::GAME_PASS).unwrap();
+  }
+
+  // the main pass shows the texture the game pass has drawn
+  graph
+    .add_node_edge(node::GAME_PASS, base::node::MAIN_PASS)
+    .unwrap();
+}
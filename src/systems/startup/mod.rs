@@ -1,3 +1,4 @@
+pub mod graph;
 pub mod player;
-// pub mod ui;
+pub mod ui;
 pub mod world;
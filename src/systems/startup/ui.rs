@@ -1,10 +1,16 @@
 use bevy::prelude::*;
 
-use super::super::super::components::{CoinsText, TimerText};
-use super::super::super::resources::GameState;
+use super::super::super::components::{
+  BossBar, BossBarFill, HealthText, InventoryText, OxygenText, TimerText, TipText,
+};
+use super::super::super::constants::PLAYER_HEALTH;
 
-pub fn ui(commands: &mut Commands, state: ResMut<GameState>, asset_server: Res<AssetServer>) {
-  // Coins counter
+pub fn ui(
+  commands: &mut Commands,
+  asset_server: Res<AssetServer>,
+  mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+  // Inventory, filled in once the level is loaded
   commands
     .spawn(TextBundle {
       style: Style {
@@ -17,18 +23,18 @@ pub fn ui(commands: &mut Commands, state: ResMut<GameState>, asset_server: Res<A
         },
         ..Default::default()
       },
-      text: Text {
-        value: format!("Coins: {}", state.coins),
-        font: asset_server.load("font/SBH1.ttf"),
-        style: TextStyle {
+      text: Text::with_section(
+        String::new(),
+        TextStyle {
+          font: asset_server.load("font/SBH1.ttf"),
           font_size: 48.0,
           color: Color::rgb(34., 32., 52.),
-          ..Default::default()
         },
-      },
+        Default::default(),
+      ),
       ..Default::default()
     })
-    .with(CoinsText);
+    .with(InventoryText);
 
   // Timer
   commands
@@ -43,17 +49,126 @@ pub fn ui(commands: &mut Commands, state: ResMut<GameState>, asset_server: Res<A
         },
         ..Default::default()
       },
-      text: Text {
-        value: format!("Time: {}", 0.),
-        font: asset_server.load("font/SBH1.ttf"),
-        style: TextStyle {
+      text: Text::with_section(
+        format!("Time: {}", 0.),
+        TextStyle {
+          font: asset_server.load("font/SBH1.ttf"),
           font_size: 48.0,
           color: Color::rgb(34., 32., 52.),
-          ..Default::default()
         },
-      },
+        Default::default(),
+      ),
       ..Default::default()
     })
     .with(TimerText)
     .with(Timer::from_seconds(0.1, true));
+
+  // Health
+  commands
+    .spawn(TextBundle {
+      style: Style {
+        align_self: AlignSelf::FlexEnd,
+        position_type: PositionType::Absolute,
+        position: Rect {
+          top: Val::Px(96.0),
+          left: Val::Px(24.0),
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+      text: Text::with_section(
+        format!("Health: {}/{}", PLAYER_HEALTH, PLAYER_HEALTH),
+        TextStyle {
+          font: asset_server.load("font/SBH1.ttf"),
+          font_size: 48.0,
+          color: Color::rgb(34., 32., 52.),
+        },
+        Default::default(),
+      ),
+      ..Default::default()
+    })
+    .with(HealthText);
+
+  // Oxygen, shown while the player is running out of it under water
+  commands
+    .spawn(TextBundle {
+      style: Style {
+        align_self: AlignSelf::FlexEnd,
+        position_type: PositionType::Absolute,
+        position: Rect {
+          top: Val::Px(136.0),
+          left: Val::Px(24.0),
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+      text: Text::with_section(
+        String::new(),
+        TextStyle {
+          font: asset_server.load("font/SBH1.ttf"),
+          font_size: 48.0,
+          color: Color::rgb(34., 32., 52.),
+        },
+        Default::default(),
+      ),
+      ..Default::default()
+    })
+    .with(OxygenText);
+
+  // Tips from trigger zones
+  commands
+    .spawn(TextBundle {
+      style: Style {
+        align_self: AlignSelf::FlexEnd,
+        position_type: PositionType::Absolute,
+        position: Rect {
+          bottom: Val::Px(56.0),
+          left: Val::Px(24.0),
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+      text: Text::with_section(
+        String::new(),
+        TextStyle {
+          font: asset_server.load("font/SBH1.ttf"),
+          font_size: 48.0,
+          color: Color::rgb(34., 32., 52.),
+        },
+        Default::default(),
+      ),
+      ..Default::default()
+    })
+    .with(TipText);
+
+  // Boss health bar, hidden until a boss fight starts
+  commands
+    .spawn(NodeBundle {
+      style: Style {
+        display: Display::None,
+        position_type: PositionType::Absolute,
+        position: Rect {
+          bottom: Val::Px(24.0),
+          left: Val::Percent(25.0),
+          ..Default::default()
+        },
+        size: Size::new(Val::Percent(50.0), Val::Px(16.0)),
+        ..Default::default()
+      },
+      material: materials.add(Color::rgb(0.13, 0.12, 0.2).into()),
+      ..Default::default()
+    })
+    .with(BossBar)
+    .with_children(|parent| {
+      parent
+        .spawn(NodeBundle {
+          style: Style {
+            size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+            ..Default::default()
+          },
+          material: materials.add(Color::rgb(0.8, 0.15, 0.15).into()),
+          ..Default::default()
+        })
+        .with(BossBarFill);
+    });
 }
@@ -1,11 +1,28 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::Arc;
 
 use bevy::prelude::*;
-use tiled::parse_file;
+use tiled::{parse_file, PropertyValue};
 
-use super::super::super::components::{Background, Block, Camera, CheckPoint, Coin, Hook};
-use super::super::super::constants::{WINDOW_HEIGHT, WINDOW_WIDTH};
-use super::super::super::resources::Options;
+use super::super::super::assets::Parallax;
+use super::super::super::components::{
+  AnimationGraph, Animator, Arena, Background, Block, Boss, Breakable, Camera, CheckPoint,
+  Climbable, Collectible, CollectibleKind, CrumbleState, Crumbling, Enemy, EnemyBehaviour, Facing,
+  Force, ForceField, Gate, Hazard, HazardKind, Heal, Health, Hitbox, Hook, Hurtbox, Link, Lock,
+  Screen, Spawner, Surface, Switch, SwitchKind, Team, Throwable, Trigger, Water,
+};
+use super::super::super::constants::{
+  BOSS_HEALTH, CRUMBLE_DELAY, ENEMY_ANIMATION_FPS, ENEMY_BLOWGUN_RANGE, ENEMY_BLOWGUN_RELOAD,
+  ENEMY_CHASER_SPEED, ENEMY_DAMAGE, ENEMY_FLYER_SPEED, ENEMY_HEALTH, ENEMY_INVULNERABILITY,
+  ENEMY_PATROL_SPEED, HAZARD_KNOCKBACK_X, HAZARD_KNOCKBACK_Y, LEVEL, PARALLAX_FILE, WINDOW_HEIGHT,
+  WINDOW_WIDTH,
+};
+use super::super::super::events::GameEvent;
+use super::super::super::render::{GAME_CAMERA, RENDER_TARGET};
+use super::super::super::resources::{CameraLock, Inventory, Relics, Splash, Sprites};
+use super::super::super::utils::map_to_world;
+use super::super::enemies::spawn_enemy;
 
 #[derive(Bundle)]
 struct BlockComponent {
@@ -14,39 +31,54 @@ struct BlockComponent {
 
 pub fn world(
   commands: &mut Commands,
-  options: Res<Options>,
-  window: Res<WindowDescriptor>,
+  sprites: Res<Sprites>,
+  mut inventory: ResMut<Inventory>,
+  mut camera_lock: ResMut<CameraLock>,
+  mut splash: ResMut<Splash>,
+  relics: Res<Relics>,
   asset_server: Res<AssetServer>,
   mut materials: ResMut<Assets<ColorMaterial>>,
   mut texture_atlases: ResMut<Assets<TextureAtlas>>,
 ) {
-  let scale = options.scale as f32;
-
   // Camera
 
+  // the game camera draws into the low resolution texture, the window camera
+  // only looks at the screen sprite upscaling it
+  let mut game_camera = OrthographicCameraBundle::new_2d();
+  game_camera.camera.name = Some(GAME_CAMERA.to_string());
+
   commands
     .spawn(UiCameraBundle::default())
     .spawn(OrthographicCameraBundle::new_2d())
-    .with(Camera);
+    .spawn(game_camera)
+    .with(Camera::default())
+    .spawn(SpriteBundle {
+      material: materials.add(ColorMaterial::texture(RENDER_TARGET.typed())),
+      sprite: Sprite::new(Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT)),
+      ..Default::default()
+    })
+    .with(Screen);
 
   // Background
 
-  for i in 1..=5 {
-    let bg_handle = asset_server.load(format!("background/plx-{}.png", i).as_str());
+  let parallax = Parallax::load(PARALLAX_FILE).expect("Can't load parallax layers");
+
+  for layer in parallax.layers.iter() {
+    let material = materials.add(asset_server.load(layer.texture.as_str()).into());
 
-    for j in 0..=1 {
+    // two tiles cover the window, the parallax system moves them around the camera
+    for tile in 0..=1 {
       commands
         .spawn(SpriteBundle {
-          material: materials.add(bg_handle.clone().into()),
-          transform: Transform {
-            translation: Vec3::new(scale * WINDOW_WIDTH * j as f32, 0.0, i as f32),
-            scale: Vec3::splat(scale),
-            ..Default::default()
-          },
+          material: material.clone(),
+          transform: Transform::from_translation(Vec3::new(0.0, 0.0, layer.z)),
           ..Default::default()
         })
         .with(Background {
-          acceleration: 0.2 * (5 - i) as f32,
+          factor: Vec2::new(layer.factor, layer.vertical_factor),
+          offset: Vec2::new(layer.offset.0, layer.offset.1),
+          width: layer.width,
+          tile,
         });
     }
   }
@@ -57,11 +89,57 @@ pub fn world(
   let block_atlas = TextureAtlas::from_grid(block_handle, Vec2::new(16.0, 16.0), 48, 23);
   let block_atlas_handle = texture_atlases.add(block_atlas);
 
-  let map = parse_file(&Path::new("assets/level1.tmx")).unwrap();
+  let map = parse_file(&Path::new(&format!("assets/{}.tmx", LEVEL))).unwrap();
 
   let tile_width = 16.0;
   let tile_height = 16.0;
 
+  let mut surfaces: HashMap<u32, Surface> = HashMap::new();
+  let mut waters: HashSet<u32> = HashSet::new();
+  let mut hazards: HashMap<u32, HazardKind> = HashMap::new();
+  let mut breakables: HashSet<u32> = HashSet::new();
+  let mut crumblings: HashSet<u32> = HashSet::new();
+
+  let debris_material = materials.add(Color::rgb(0.45, 0.3, 0.15).into());
+  let water_material = materials.add(Color::rgba(0.25, 0.55, 0.8, 0.5).into());
+
+  // splashes of water tiles and water objects look the same
+  splash.material = water_material.clone();
+
+  for tileset in map.tilesets.iter() {
+    for tile in tileset.tiles.iter() {
+      let gid = tileset.first_gid + tile.id;
+
+      if let Some(PropertyValue::StringValue(name)) = tile.properties.get("surface") {
+        surfaces.insert(gid, Surface::from_name(name));
+      }
+
+      if let Some(PropertyValue::BoolValue(true)) = tile.properties.get("water") {
+        waters.insert(gid);
+      }
+
+      if let Some(PropertyValue::BoolValue(true)) = tile.properties.get("breakable") {
+        breakables.insert(gid);
+      }
+
+      if let Some(PropertyValue::BoolValue(true)) = tile.properties.get("crumbling") {
+        crumblings.insert(gid);
+      }
+
+      if let Some(PropertyValue::StringValue(name)) = tile.properties.get("hazard") {
+        if let Some(kind) = HazardKind::from_name(name) {
+          hazards.insert(gid, kind);
+        }
+      }
+    }
+  }
+
+  // the camera never shows anything outside of the map
+  camera_lock.level = Some((
+    map_to_world(0.0, tile_height * map.height as f32),
+    map_to_world(tile_width * map.width as f32, 0.0),
+  ));
+
   for layer in map.layers.iter() {
     for i in 0..map.width {
       for j in 0..map.height {
@@ -74,103 +152,491 @@ pub fn world(
           continue;
         }
 
-        let tile_x = (tile_width * i as f32) * scale;
-        let tile_y = (WINDOW_HEIGHT / 2.0 - tile_height * j as f32) * scale;
+        let tile_center = map_to_world(
+          tile_width * (i as f32 + 0.5),
+          tile_height * (j as f32 + 0.5),
+        );
 
-        commands
-          .spawn(SpriteSheetBundle {
-            transform: Transform {
-              translation: Vec3::new(tile_x, tile_y, 10.0),
-              scale: Vec3::splat(scale),
-              ..Default::default()
+        let is_water = waters.contains(&tile.gid);
+        let z = if is_water { 11.0 } else { 10.0 };
+
+        // water is drawn semi-transparent over the other tiles
+        commands.spawn(SpriteSheetBundle {
+          transform: Transform::from_translation(tile_center.extend(z)),
+          sprite: TextureAtlasSprite {
+            index: tile.gid - 1,
+            color: if is_water {
+              Color::rgba(1.0, 1.0, 1.0, 0.6)
+            } else {
+              Color::WHITE
             },
-            sprite: TextureAtlasSprite::new(tile.gid - 1),
-            texture_atlas: block_atlas_handle.clone(),
+          },
+          visible: Visible {
+            is_transparent: is_water,
             ..Default::default()
-          })
-          .with(Block {
-            size: Vec2::new(tile_width * scale, tile_height * scale),
+          },
+          texture_atlas: block_atlas_handle.clone(),
+          ..Default::default()
+        });
+
+        // water and hazard tiles are not solid, the player gets into them
+        if is_water {
+          commands.with(Water {
+            size: Vec2::new(tile_width, tile_height),
           });
+        } else if let Some(kind) = hazards.get(&tile.gid) {
+          commands.with(Hazard {
+            size: Vec2::new(tile_width, tile_height),
+            damage: 1,
+            kind: *kind,
+          });
+        } else {
+          let size = Vec2::new(tile_width, tile_height);
+          let surface = *surfaces.get(&tile.gid).unwrap_or(&Surface::Ground);
+
+          commands.with(Block { size, surface });
+
+          if breakables.contains(&tile.gid) {
+            commands.with(Breakable {
+              debris: debris_material.clone(),
+            });
+          }
+
+          if crumblings.contains(&tile.gid) {
+            commands.with(Crumbling {
+              size,
+              surface,
+              origin: tile_center.extend(10.0),
+              state: CrumbleState::Solid,
+              timer: Timer::from_seconds(CRUMBLE_DELAY, false),
+              velocity: 0.0,
+              debris: debris_material.clone(),
+            });
+          }
+        }
       }
     }
   }
 
-  let coin_handle = asset_server.load("objects/coin.png");
-  let coin_atlas = TextureAtlas::from_grid(coin_handle, Vec2::new(16.0, 16.0), 8, 1);
-  let coin_atlas_handle = texture_atlases.add(coin_atlas);
+  let collectible_graph = Arc::new(AnimationGraph::looping(12.5));
 
-  let totem_handle = asset_server.load("objects/totem.png");
-  let totem_atlas = TextureAtlas::from_grid(totem_handle, Vec2::new(19.0, 27.0), 1, 1);
-  let totem_atlas_handle = texture_atlases.add(totem_atlas);
+  // level exit stays locked until the boss of the level is defeated
+  let has_boss = map
+    .object_groups
+    .iter()
+    .any(|group| group.objects.iter().any(|object| object.obj_type == "boss"));
 
   // Objects
 
   for group in map.object_groups.iter() {
     for object in group.objects.iter() {
-      // Coins
-      if object.obj_type == "coin" {
+      // Coins, gems, keys and secret relics
+      if let Some(kind) = CollectibleKind::from_name(&object.obj_type) {
+        let value = property_f32(object, "value", 1.0) as u32;
+
+        inventory.add_total(kind, value);
+
+        // relics found in earlier runs are already in the inventory
+        if kind == CollectibleKind::Relic && relics.is_found(&Relics::key(LEVEL, object.id)) {
+          inventory.add(kind, value);
+          continue;
+        }
+
+        let texture_atlas = sprites.handle(&format!("object_{}", object.obj_type));
+
         commands
           .spawn(SpriteSheetBundle {
             sprite: TextureAtlasSprite::new(0),
-            transform: Transform {
-              translation: Vec3::new(
-                scale * object.x,
-                window.height as f32 / 2.0 - scale * object.y,
-                10.0,
-              ),
-              scale: Vec3::splat(scale),
-              ..Default::default()
+            transform: Transform::from_translation(object_center(object).extend(10.0)),
+            texture_atlas,
+            ..Default::default()
+          })
+          .with(Collectible {
+            size: Vec2::new(object.width, object.height),
+            kind,
+            value,
+            id: object.id,
+          })
+          .with(Animator::new(collectible_graph.clone()));
+      }
+
+      // Doors, gates and platforms opened by switches and keys
+      if ["door", "gate", "platform"].contains(&object.obj_type.as_str()) {
+        let size = Vec2::new(object.width, object.height);
+        let is_open = property_bool(object, "open", false);
+
+        commands
+          .spawn(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(0),
+            transform: Transform::from_translation(object_center(object).extend(10.0)),
+            visible: Visible {
+              is_visible: !is_open,
+              is_transparent: true,
             },
-            texture_atlas: coin_atlas_handle.clone(),
+            texture_atlas: sprites.handle(&format!("object_{}", object.obj_type)),
             ..Default::default()
           })
-          .with(Coin {
-            size: Vec2::new(object.width * scale, object.height * scale),
+          .with(Gate { size, is_open })
+          .with(Link { id: object.id });
+
+        if !is_open {
+          commands.with(Block {
+            size,
+            surface: Surface::Ground,
+          });
+        }
+
+        if object.obj_type == "door" && property_bool(object, "locked", true) {
+          commands.with(Lock);
+        }
+      }
+
+      // Pressure plates and levers
+      if object.obj_type == "plate" || object.obj_type == "lever" {
+        let kind = if object.obj_type == "plate" {
+          SwitchKind::Plate
+        } else {
+          SwitchKind::Lever
+        };
+
+        commands
+          .spawn(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(0),
+            transform: Transform::from_translation(object_center(object).extend(10.0)),
+            texture_atlas: sprites.handle(&format!("object_{}", object.obj_type)),
+            ..Default::default()
           })
-          .with(Timer::from_seconds(0.08, true));
+          .with(Switch {
+            size: Vec2::new(object.width, object.height),
+            kind,
+            is_on: false,
+            targets: property_ids(object, "target"),
+            is_inverted: property_bool(object, "invert", false),
+          })
+          .with(Link { id: object.id });
+      }
+
+      // Healing fruits
+      if object.obj_type == "fruit" {
+        commands
+          .spawn(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(0),
+            transform: Transform::from_translation(object_center(object).extend(10.0)),
+            texture_atlas: sprites.handle("object_fruit"),
+            ..Default::default()
+          })
+          .with(Heal {
+            size: Vec2::new(object.width, object.height),
+            amount: property_f32(object, "heal", 1.0) as u8,
+          });
+      }
+
+      // Stones to throw
+      if object.obj_type == "stone" {
+        commands
+          .spawn(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(0),
+            transform: Transform::from_translation(object_center(object).extend(10.0)),
+            texture_atlas: sprites.handle("projectile_stone"),
+            ..Default::default()
+          })
+          .with(Throwable {
+            size: Vec2::new(object.width, object.height),
+          });
       }
 
       // Hooks
       if object.obj_type == "hook" {
         commands
           .spawn(SpriteBundle {
-            transform: Transform {
-              translation: Vec3::new(
-                scale * object.x,
-                window.height as f32 / 2.0 - scale * object.y,
-                10.0,
-              ),
-              scale: Vec3::splat(scale),
+            transform: Transform::from_translation(object_center(object).extend(10.0)),
+            ..Default::default()
+          })
+          .with(Hook {
+            size: Vec2::new(object.width, object.height),
+          });
+      }
+
+      // Ladders and vines
+      if object.obj_type == "ladder" || object.obj_type == "vine" {
+        commands
+          .spawn(SpriteBundle {
+            transform: Transform::from_translation(object_center(object).extend(10.0)),
+            ..Default::default()
+          })
+          .with(Climbable {
+            size: Vec2::new(object.width, object.height),
+          });
+      }
+
+      // Water
+      if object.obj_type == "water" {
+        commands
+          .spawn(SpriteBundle {
+            material: water_material.clone(),
+            sprite: Sprite::new(Vec2::new(object.width, object.height)),
+            visible: Visible {
+              is_transparent: true,
               ..Default::default()
             },
+            transform: Transform::from_translation(object_center(object).extend(11.0)),
             ..Default::default()
           })
-          .with(Hook {
-            size: Vec2::new(object.width * scale, object.height * scale),
+          .with(Water {
+            size: Vec2::new(object.width, object.height),
+          });
+      }
+
+      // Springs, bounce mushrooms and wind zones
+      let force = match object.obj_type.as_str() {
+        "spring" => Some(Force::Impulse(Vec3::new(
+          property_f32(object, "impulse_x", 0.0),
+          property_f32(object, "impulse_y", 6.0),
+          0.0,
+        ))),
+        "mushroom" => Some(Force::Bounce(property_f32(object, "restitution", 0.9))),
+        "wind" => Some(Force::Wind(Vec3::new(
+          property_f32(object, "force_x", 4.0),
+          property_f32(object, "force_y", 0.0),
+          0.0,
+        ))),
+        _ => None,
+      };
+
+      if let Some(force) = force {
+        commands
+          .spawn(SpriteBundle {
+            transform: Transform::from_translation(object_center(object).extend(10.0)),
+            ..Default::default()
+          })
+          .with(ForceField {
+            size: Vec2::new(object.width, object.height),
+            force,
+          });
+      }
+
+      // Spikes, thorns and pits
+      if let Some(kind) = HazardKind::from_name(&object.obj_type) {
+        commands
+          .spawn(SpriteBundle {
+            transform: Transform::from_translation(object_center(object).extend(10.0)),
+            ..Default::default()
+          })
+          .with(Hazard {
+            size: Vec2::new(object.width, object.height),
+            damage: property_f32(object, "damage", 1.0) as u8,
+            kind,
+          });
+      }
+
+      // Enemies
+      if object.obj_type == "enemy" {
+        let translation = object_center(object).extend(14.0);
+
+        let (behaviour, sprite_key) = match property_string(object, "behaviour").as_str() {
+          "flyer" => (
+            EnemyBehaviour::Flyer {
+              speed: property_f32(object, "speed", ENEMY_FLYER_SPEED),
+              range: property_f32(object, "range", 48.0),
+              amplitude: property_f32(object, "amplitude", 16.0),
+              frequency: property_f32(object, "frequency", 2.0),
+              origin: translation,
+              time: 0.0,
+            },
+            "enemy_flyer",
+          ),
+          "chaser" => (
+            EnemyBehaviour::Chaser {
+              speed: property_f32(object, "speed", ENEMY_CHASER_SPEED),
+              range: property_f32(object, "range", 96.0),
+            },
+            "enemy_chaser",
+          ),
+          "blowgun" => (
+            EnemyBehaviour::Blowgun {
+              range: property_f32(object, "range", ENEMY_BLOWGUN_RANGE),
+              reload: Timer::from_seconds(
+                property_f32(object, "reload", ENEMY_BLOWGUN_RELOAD),
+                false,
+              ),
+            },
+            "enemy_blowgun",
+          ),
+          _ => (
+            EnemyBehaviour::Patrol {
+              speed: property_f32(object, "speed", ENEMY_PATROL_SPEED),
+            },
+            "enemy_patrol",
+          ),
+        };
+
+        let enemy = Enemy {
+          size: Vec2::new(object.width, object.height),
+          velocity: Vec3::new(0.0, 0.0, 0.0),
+          behaviour,
+        };
+        let health = property_f32(object, "health", ENEMY_HEALTH as f32) as u8;
+
+        // dormant enemies wait for a link signal, e.g. from a trigger zone
+        if property_bool(object, "dormant", false) {
+          commands
+            .spawn((
+              Transform::from_translation(translation),
+              GlobalTransform::default(),
+            ))
+            .with(Spawner {
+              enemy,
+              health,
+              sprite_key: sprite_key.to_string(),
+            })
+            .with(Link { id: object.id });
+        } else {
+          spawn_enemy(
+            commands,
+            sprites.handle(sprite_key),
+            translation,
+            enemy,
+            health,
+          );
+        }
+      }
+
+      // Trigger zones
+      if object.obj_type == "trigger" {
+        let size = Vec2::new(object.width, object.height);
+        let center = object_center(object);
+        let bounds = (center - size / 2.0, center + size / 2.0);
+
+        let actions = |name: &str| -> Vec<GameEvent> {
+          property_string(object, name)
+            .split(';')
+            .filter_map(|action| GameEvent::parse(action, bounds))
+            .collect()
+        };
+
+        commands
+          .spawn((
+            Transform::from_translation(center.extend(10.0)),
+            GlobalTransform::default(),
+          ))
+          .with(Trigger {
+            size,
+            on_enter: actions("enter"),
+            on_stay: actions("stay"),
+            on_exit: actions("exit"),
+            is_inside: false,
+            is_once: property_bool(object, "once", false),
+          });
+      }
+
+      // Bosses and their arenas
+      if object.obj_type == "boss" {
+        let size = Vec2::new(object.width, object.height);
+
+        commands
+          .spawn(SpriteSheetBundle {
+            sprite: TextureAtlasSprite::new(0),
+            transform: Transform::from_translation(object_center(object).extend(14.0)),
+            texture_atlas: sprites.handle("enemy_boss"),
+            ..Default::default()
+          })
+          .with(Enemy {
+            size,
+            velocity: Vec3::new(0.0, 0.0, 0.0),
+            behaviour: EnemyBehaviour::Boss,
+          })
+          .with(Boss::jungle_guardian())
+          .with(Facing::Right)
+          .with(Health::new(BOSS_HEALTH, ENEMY_INVULNERABILITY))
+          .with(Hurtbox {
+            size,
+            team: Team::Enemy,
+          })
+          .with(Hitbox {
+            size,
+            damage: ENEMY_DAMAGE,
+            knockback: Vec2::new(HAZARD_KNOCKBACK_X, HAZARD_KNOCKBACK_Y),
+            team: Team::Enemy,
+          })
+          .with(Animator::new(Arc::new(AnimationGraph::walker(
+            ENEMY_ANIMATION_FPS,
+          ))));
+      }
+
+      if object.obj_type == "arena" {
+        commands
+          .spawn((
+            Transform::from_translation(object_center(object).extend(10.0)),
+            GlobalTransform::default(),
+          ))
+          .with(Arena {
+            size: Vec2::new(object.width, object.height),
           });
       }
 
       // Checkpoints
       if object.obj_type == "checkpoint" {
+        let is_exit = property_bool(object, "exit", true);
+
         commands
           .spawn(SpriteSheetBundle {
             sprite: TextureAtlasSprite::new(0),
-            transform: Transform {
-              translation: Vec3::new(
-                scale * object.x,
-                window.height as f32 / 2.0 - scale * object.y,
-                9.0,
-              ),
-              scale: Vec3::splat(scale),
-              ..Default::default()
-            },
-            texture_atlas: totem_atlas_handle.clone(),
+            transform: Transform::from_translation(object_center(object).extend(9.0)),
+            texture_atlas: sprites.handle("object_totem"),
             ..Default::default()
           })
           .with(CheckPoint {
-            size: Vec2::new(object.width * scale, object.height * scale),
+            size: Vec2::new(object.width, object.height),
+            is_exit,
+            is_locked: has_boss && is_exit,
           });
       }
     }
   }
 }
+
+/// Center of an object in world units, tile objects are anchored at their bottom
+/// left corner in Tiled and the other objects at their top left corner
+fn object_center(object: &tiled::Object) -> Vec2 {
+  let top = if object.gid != 0 {
+    object.y - object.height
+  } else {
+    object.y
+  };
+
+  map_to_world(object.x + object.width / 2.0, top + object.height / 2.0)
+}
+
+fn property_f32(object: &tiled::Object, name: &str, default: f32) -> f32 {
+  match object.properties.get(name) {
+    Some(PropertyValue::FloatValue(value)) => *value,
+    Some(PropertyValue::IntValue(value)) => *value as f32,
+    _ => default,
+  }
+}
+
+fn property_bool(object: &tiled::Object, name: &str, default: bool) -> bool {
+  match object.properties.get(name) {
+    Some(PropertyValue::BoolValue(value)) => *value,
+    _ => default,
+  }
+}
+
+fn property_string(object: &tiled::Object, name: &str) -> String {
+  match object.properties.get(name) {
+    Some(PropertyValue::StringValue(value)) => value.clone(),
+    _ => String::new(),
+  }
+}
+
+/// Object references, either a single id or a comma separated list like "12,15"
+fn property_ids(object: &tiled::Object, name: &str) -> Vec<u32> {
+  match object.properties.get(name) {
+    Some(PropertyValue::IntValue(value)) => vec![*value as u32],
+    Some(PropertyValue::StringValue(value)) => value
+      .split(',')
+      .filter_map(|id| id.trim().parse().ok())
+      .collect(),
+    _ => Vec::new(),
+  }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use super::super::components::{
+  CollectibleKind, Enemy, Gate, Link, Lock, Player, Switch, SwitchKind,
+};
+use super::super::events::{LinkEvent, Signal};
+use super::super::resources::Inventory;
+use super::super::utils::collide_aabb;
+
+pub fn switches(
+  commands: &mut Commands,
+  keyboard_input: Res<Input<KeyCode>>,
+  mut inventory: ResMut<Inventory>,
+  mut link_events: ResMut<Events<LinkEvent>>,
+  mut player_query: Query<(&Player, &Transform)>,
+  mut enemy_query: Query<(&Enemy, &Transform)>,
+  mut switch_query: Query<(&mut Switch, &Transform, &mut TextureAtlasSprite)>,
+  mut lock_query: Query<(Entity, &Gate, &Link, &Lock, &Transform)>,
+) {
+  for (player, player_transform) in player_query.iter_mut() {
+    let player_translation = player_transform.translation;
+
+    // Pressure plates stay on while something stands on them, levers flip on E
+
+    for (mut switch, switch_transform, mut sprite) in switch_query.iter_mut() {
+      let switch_translation = switch_transform.translation;
+      let is_touching = collide_aabb(
+        player_translation,
+        player.size,
+        switch_translation,
+        switch.size,
+      )
+      .is_some();
+
+      let is_on = match switch.kind {
+        SwitchKind::Plate => {
+          is_touching
+            || enemy_query.iter_mut().any(|(enemy, enemy_transform)| {
+              collide_aabb(
+                enemy_transform.translation,
+                enemy.size,
+                switch_translation,
+                switch.size,
+              )
+              .is_some()
+            })
+        }
+        SwitchKind::Lever => {
+          if is_touching && keyboard_input.just_pressed(KeyCode::E) {
+            !switch.is_on
+          } else {
+            switch.is_on
+          }
+        }
+      };
+
+      if is_on == switch.is_on {
+        continue;
+      }
+
+      switch.is_on = is_on;
+      sprite.index = is_on as u32;
+
+      for target in switch.targets.iter() {
+        link_events.send(LinkEvent {
+          target: *target,
+          signal: switch.signal(),
+        });
+      }
+    }
+
+    // Locked doors take a key from the inventory when the player walks into them
+
+    for (lock_entity, gate, link, _lock, lock_transform) in lock_query.iter_mut() {
+      let reach = gate.size + Vec2::new(player.size.x / 2.0, 0.0);
+      let collision = collide_aabb(
+        player_translation,
+        player.size,
+        lock_transform.translation,
+        reach,
+      );
+
+      if collision.is_some() && !gate.is_open && inventory.take(CollectibleKind::Key, 1) {
+        commands.remove_one::<Lock>(lock_entity);
+
+        link_events.send(LinkEvent {
+          target: link.id,
+          signal: Signal::On,
+        });
+      }
+    }
+  }
+}
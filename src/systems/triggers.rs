@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+
+use super::super::components::{Link, Player, Trigger};
+use super::super::constants::CAMERA_PAN_DURATION;
+use super::super::events::{GameEvent, LinkEvent};
+use super::super::resources::{CameraEffects, CameraLock, Tip};
+use super::super::utils::collide_aabb;
+
+pub fn triggers(
+  commands: &mut Commands,
+  mut game_events: ResMut<Events<GameEvent>>,
+  mut player_query: Query<(&Player, &Transform)>,
+  mut trigger_query: Query<(Entity, &mut Trigger, &Transform)>,
+) {
+  for (player, player_transform) in player_query.iter_mut() {
+    for (trigger_entity, mut trigger, trigger_transform) in trigger_query.iter_mut() {
+      let collision = collide_aabb(
+        player_transform.translation,
+        player.size,
+        trigger_transform.translation,
+        trigger.size,
+      );
+
+      let is_inside = collision.is_some();
+
+      let actions = match (trigger.is_inside, is_inside) {
+        (false, true) => &trigger.on_enter,
+        (true, true) => &trigger.on_stay,
+        (true, false) => &trigger.on_exit,
+        (false, false) => continue,
+      };
+
+      for action in actions.iter() {
+        game_events.send(action.clone());
+      }
+
+      if trigger.is_inside && !is_inside && trigger.is_once {
+        commands.despawn(trigger_entity);
+      }
+
+      trigger.is_inside = is_inside;
+    }
+  }
+}
+
+/// Carries out game events published by triggers
+pub fn game_events(
+  asset_server: Res<AssetServer>,
+  audio: Res<Audio>,
+  mut camera_lock: ResMut<CameraLock>,
+  mut camera_effects: ResMut<CameraEffects>,
+  mut tip: ResMut<Tip>,
+  mut game_events: ResMut<Events<GameEvent>>,
+  mut link_events: ResMut<Events<LinkEvent>>,
+  mut link_query: Query<(&Link, &Transform)>,
+) {
+  for event in game_events.drain() {
+    match event {
+      GameEvent::ShowTip(text) => {
+        tip.text = text;
+        tip.timer.reset();
+      }
+      GameEvent::PlayMusic(path) => {
+        audio.play(asset_server.load(path.as_str()));
+      }
+      GameEvent::LockCamera(min, max) => {
+        camera_lock.bounds = Some((min, max));
+      }
+      GameEvent::UnlockCamera => {
+        camera_lock.bounds = None;
+      }
+      GameEvent::Link(target, signal) => {
+        link_events.send(LinkEvent { target, signal });
+      }
+      GameEvent::PanCamera(target) => {
+        for (link, link_transform) in link_query.iter_mut() {
+          if link.id == target {
+            camera_effects.pan(link_transform.translation.truncate(), CAMERA_PAN_DURATION);
+          }
+        }
+      }
+    }
+  }
+}
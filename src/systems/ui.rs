@@ -1,16 +1,58 @@
 use bevy::prelude::*;
 
-use super::super::components::{CoinsText, TimerText};
-use super::super::resources::{Game, GameState};
+use super::super::components::{
+  Boss, BossBar, BossBarFill, CollectibleKind, Health, HealthText, InventoryText, OxygenText,
+  Player, TimerText, TipText,
+};
+use super::super::constants::PLAYER_OXYGEN;
+use super::super::resources::{CameraLock, Game, GameState, Inventory, Tip};
 
 pub fn ui(
   time: Res<Time>,
   mut state: ResMut<GameState>,
-  mut coins_query: Query<(&mut Text, &CoinsText)>,
+  inventory: Res<Inventory>,
+  mut tip: ResMut<Tip>,
+  mut inventory_query: Query<(&mut Text, &InventoryText)>,
   mut timer_query: Query<(&mut Text, &mut Timer, &TimerText)>,
+  mut health_query: Query<(&mut Text, &HealthText)>,
+  mut oxygen_query: Query<(&mut Text, &OxygenText)>,
+  mut tip_query: Query<(&mut Text, &TipText)>,
+  mut player_query: Query<(&Player, &Health)>,
+  camera_lock: Res<CameraLock>,
+  mut boss_query: Query<(&Boss, &Health)>,
+  mut boss_bar_queries: QuerySet<(
+    Query<(&BossBar, &mut Style)>,
+    Query<(&BossBarFill, &mut Style)>,
+  )>,
 ) {
-  for (mut text, _tag) in coins_query.iter_mut() {
-    text.value = format!("Coins: {}", state.coins);
+  for (mut text, _tag) in inventory_query.iter_mut() {
+    let mut value = format!(
+      "Coins: {}/{}",
+      inventory.count(CollectibleKind::Coin),
+      inventory.total(CollectibleKind::Coin)
+    );
+
+    if inventory.total(CollectibleKind::Gem) > 0 {
+      value += &format!(
+        "  Gems: {}/{}",
+        inventory.count(CollectibleKind::Gem),
+        inventory.total(CollectibleKind::Gem)
+      );
+    }
+
+    if inventory.count(CollectibleKind::Key) > 0 {
+      value += &format!("  Keys: {}", inventory.count(CollectibleKind::Key));
+    }
+
+    if inventory.total(CollectibleKind::Relic) > 0 {
+      value += &format!(
+        "  Relics: {}/{}",
+        inventory.count(CollectibleKind::Relic),
+        inventory.total(CollectibleKind::Relic)
+      );
+    }
+
+    text.sections[0].value = value;
   }
 
   for (mut text, mut timer, _tag) in timer_query.iter_mut() {
@@ -18,8 +60,56 @@ pub fn ui(
     if let Game::Started = state.game {
       if timer.finished() {
         state.timer += timer.duration();
-        text.value = format!("Timer: {:.1$}", state.timer, 1);
+        text.sections[0].value = format!("Timer: {:.1$}", state.timer, 1);
       }
     }
   }
+
+  for (player, health) in player_query.iter_mut() {
+    for (mut text, _tag) in health_query.iter_mut() {
+      text.sections[0].value = format!("Health: {}/{}", health.current, health.max);
+    }
+
+    // oxygen is only shown once the player starts using it
+    for (mut text, _tag) in oxygen_query.iter_mut() {
+      text.sections[0].value = if player.oxygen < PLAYER_OXYGEN {
+        format!(
+          "Oxygen: {:.0}%",
+          100.0 * player.oxygen.max(0.0) / PLAYER_OXYGEN
+        )
+      } else {
+        String::new()
+      };
+    }
+  }
+
+  tip.timer.tick(time.delta_seconds());
+
+  for (mut text, _tag) in tip_query.iter_mut() {
+    text.sections[0].value = if tip.timer.finished() {
+      String::new()
+    } else {
+      tip.text.clone()
+    };
+  }
+
+  // boss health bar is shown while the camera is held by a boss arena
+  let boss_health = boss_query.iter_mut().next().map(|(_boss, health)| health);
+  let is_fighting = camera_lock.arena.is_some() && boss_health.is_some();
+
+  for (_tag, mut style) in boss_bar_queries.q0_mut().iter_mut() {
+    style.display = if is_fighting {
+      Display::Flex
+    } else {
+      Display::None
+    };
+  }
+
+  if let Some(health) = boss_health {
+    let percent = 100.0 * health.current as f32 / health.max as f32;
+
+    for (_tag, mut style) in boss_bar_queries.q1_mut().iter_mut() {
+      style.size.width = Val::Percent(percent);
+    }
+  }
 }
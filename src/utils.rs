@@ -1,7 +1,16 @@
 use bevy::prelude::*;
 
+use super::components::{Block, Surface};
+use super::constants::WINDOW_HEIGHT;
+
 type Collision = Option<Vec2>;
 
+/// Converts a point of a Tiled map, in pixels down from its top left corner, to world
+/// units, the top of the map lines up with the top of the view at the start
+pub fn map_to_world(x: f32, y: f32) -> Vec2 {
+  Vec2::new(x, WINDOW_HEIGHT / 2.0 - y)
+}
+
 pub fn collide_aabb(a_position: Vec3, a_size: Vec2, b_position: Vec3, b_size: Vec2) -> Collision {
   let (a_x, a_y, _) = <(f32, f32, f32)>::from(a_position);
   let (a_w, a_h) = <(f32, f32)>::from(a_size);
@@ -17,3 +26,77 @@ pub fn collide_aabb(a_position: Vec3, a_size: Vec2, b_position: Vec3, b_size: Ve
 
   None
 }
+
+/// Moves `current` towards `target` by at most `delta` without overshooting
+pub fn approach(current: f32, target: f32, delta: f32) -> f32 {
+  if current < target {
+    (current + delta).min(target)
+  } else {
+    (current - delta).max(target)
+  }
+}
+
+/// Sides of a moving box that ran into blocks during one step
+#[derive(Default)]
+pub struct Contacts {
+  pub ground: Option<Surface>,
+  pub ceiling: bool,
+  pub wall: bool,
+}
+
+/// Moves a box by its velocity and pushes it out of the blocks it runs into,
+/// velocity is zeroed along the axis of every hit
+pub fn move_and_collide<'a>(
+  translation: Vec3,
+  size: Vec2,
+  velocity: &mut Vec3,
+  blocks: impl Iterator<Item = (&'a Block, &'a Transform)>,
+) -> (Vec3, Contacts) {
+  let mut next_translation = translation + *velocity;
+  let mut contacts = Contacts::default();
+
+  for (block, block_transform) in blocks {
+    let block_translation = block_transform.translation;
+    let collision = collide_aabb(next_translation, size, block_translation, block.size);
+
+    if let Some(collision) = collision {
+      let collision_sign_y = collision.y.signum();
+      let velocity_sign_y = velocity.y.signum();
+
+      if collision.x.abs() > collision.y.abs() && collision_sign_y == velocity_sign_y {
+        next_translation.y -= collision.y;
+
+        if velocity.y < 0.0 {
+          contacts.ground = Some(block.surface);
+        } else if velocity.y > 0.0 {
+          contacts.ceiling = true;
+        }
+
+        velocity.y = 0.0;
+      } else {
+        next_translation.x -= collision.x;
+        contacts.wall = true;
+
+        velocity.x = 0.0;
+      }
+    }
+  }
+
+  (next_translation, contacts)
+}
+
+/// Whether any of the blocks covers the point
+pub fn is_solid<'a>(
+  point: Vec3,
+  mut blocks: impl Iterator<Item = (&'a Block, &'a Transform)>,
+) -> bool {
+  blocks.any(|(block, block_transform)| {
+    collide_aabb(
+      point,
+      Vec2::new(0.0, 0.0),
+      block_transform.translation,
+      block.size,
+    )
+    .is_some()
+  })
+}